@@ -0,0 +1,50 @@
+//! Compare `Extender::boxed_fn` against boxing the result of
+//! `Extender::fn_`. Run with `cargo bench --bench boxed`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use scope_lock::Extender;
+
+const ITERATIONS: u32 = 1_000_000;
+
+type BoxedFn = Box<dyn Fn(u64) -> u64 + Send + Sync>;
+
+fn bench(name: &str, mut f: impl FnMut(u32)) {
+    // warm up
+    (0..ITERATIONS / 10).for_each(&mut f);
+    let start = Instant::now();
+    (0..ITERATIONS).for_each(&mut f);
+    println!("{name:<20} {:>8.2?}/iter", start.elapsed() / ITERATIONS);
+}
+
+fn double_boxed<'scope, F>(e: &'scope Extender<'scope, '_>, f: F) -> BoxedFn
+where
+    F: Fn(u64) -> u64 + Send + Sync + 'scope,
+{
+    let f = e.fn_(Box::new(f));
+    Box::new(f)
+}
+
+fn main() {
+    let data = [1_u64, 2, 3];
+    let f = |i: u64| data.iter().sum::<u64>() + i;
+
+    scope_lock::lock_scope(|e| {
+        bench("create double boxed", |_| {
+            drop(black_box(double_boxed(e, f)));
+        });
+        bench("create boxed_fn", |_| {
+            drop(black_box(e.boxed_fn(f)));
+        });
+
+        let double = double_boxed(e, f);
+        let single = e.boxed_fn(f);
+        bench("call double boxed", |i| {
+            black_box(double(black_box(u64::from(i))));
+        });
+        bench("call boxed_fn", |i| {
+            black_box(single(black_box(u64::from(i))));
+        });
+    });
+}
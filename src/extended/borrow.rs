@@ -0,0 +1,130 @@
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+use crate::Extender;
+use crate::extended::sync::Reference;
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Extend lifetime of a shared reference.
+    pub fn shared<T: ?Sized>(&'scope self, r: &'scope T) -> Extended<T> {
+        Extended {
+            ptr: ptr::NonNull::from(r),
+            _reference_guard: unsafe { self.rc.acquire() },
+        }
+    }
+
+    /// Extend lifetime of a mutable reference.
+    pub fn exclusive<T: ?Sized>(&'scope self, r: &'scope mut T) -> ExtendedMut<T> {
+        ExtendedMut {
+            ptr: ptr::NonNull::from(r),
+            _reference_guard: unsafe { self.rc.acquire() },
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Shared reference with an extended lifetime, returned from
+/// [`Extender::shared`].
+///
+/// Only the lifetime of the reference itself is extended, so the
+/// handle is `'static` as long as `T` is:
+///
+/// ```
+/// let data = vec![1, 2, 3];
+/// scope_lock::lock_scope(|e| {
+///     let data = e.shared(&data[..]);
+///     std::thread::spawn(move || assert_eq!(data.len(), 3));
+/// });
+/// ```
+///
+/// Lifetimes within `T` are left intact:
+///
+/// ```compile_fail
+/// fn check_static<T: 'static>(_: T) {}
+///
+/// let data = String::from("borrowed");
+/// let data: &str = &data;
+/// scope_lock::lock_scope(|e| {
+///     check_static(e.shared(&data));
+/// });
+/// ```
+///
+/// # Deadlocks
+///
+/// Being `'static`, the handle could also be returned from the scope or
+/// stored somewhere outliving it, which the compiler doesn't prevent.
+/// Since the scope waits for the handle to be dropped before it returns,
+/// that blocks forever:
+///
+/// ```no_run
+/// let data = vec![1, 2, 3];
+/// // never returns
+/// let _data = scope_lock::lock_scope(|e| e.shared(&data[..]));
+/// ```
+pub struct Extended<T: ?Sized> {
+    ptr: ptr::NonNull<T>,
+    _reference_guard: Reference,
+}
+
+unsafe impl<T: ?Sized + Sync> Send for Extended<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Extended<T> {}
+
+impl<T: ?Sized> Deref for Extended<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> Clone for Extended<T> {
+    fn clone(&self) -> Self {
+        Extended {
+            ptr: self.ptr,
+            _reference_guard: self._reference_guard.clone(),
+        }
+    }
+}
+
+/// Mutable reference with an extended lifetime, returned from
+/// [`Extender::exclusive`].
+///
+/// Same as with [`Extended`], lifetimes within `T` are left intact:
+///
+/// ```compile_fail
+/// fn check_static<T: 'static>(_: T) {}
+///
+/// let data = String::from("borrowed");
+/// let mut data: &str = &data;
+/// scope_lock::lock_scope(|e| {
+///     check_static(e.exclusive(&mut data));
+/// });
+/// ```
+///
+/// # Deadlocks
+///
+/// Returning the handle from the scope, or otherwise keeping it alive
+/// until the scope ends, blocks forever, see [`Extended`].
+pub struct ExtendedMut<T: ?Sized> {
+    ptr: ptr::NonNull<T>,
+    _reference_guard: Reference,
+    _marker: PhantomData<*mut T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for ExtendedMut<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for ExtendedMut<T> {}
+
+impl<T: ?Sized> Deref for ExtendedMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for ExtendedMut<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.ptr.as_mut() }
+    }
+}
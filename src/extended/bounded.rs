@@ -0,0 +1,91 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::Extender;
+
+use super::AssociateReference;
+use super::sync::Reference;
+
+/// Extension blocks while the limit of
+/// [`lock_scope_bounded`](crate::lock_scope_bounded) is reached. Reserve a
+/// reference to the scope beforehand to wait for it differently:
+///
+/// ```
+/// use std::sync::mpsc;
+/// use std::thread;
+///
+/// let (tx, rx) = mpsc::channel::<Box<dyn FnOnce(()) + Send>>();
+/// let worker = thread::spawn(move || rx.into_iter().for_each(|f| f(())));
+/// let data = [1, 2, 3];
+/// scope_lock::lock_scope_bounded(2, |e| {
+///     for x in &data {
+///         let reservation = match e.try_reserve() {
+///             Ok(reservation) => reservation,
+///             // producer outruns the consumer
+///             Err(scope_lock::LimitReached) => e.reserve(),
+///         };
+///         let f = reservation.fn_once(Box::new(move |()| assert!(*x > 0)));
+///         tx.send(Box::new(f)).unwrap();
+///     }
+/// });
+/// drop(tx);
+/// worker.join().unwrap();
+/// ```
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Reserve a reference to the scope, blocking while the limit is
+    /// reached.
+    pub fn reserve(&'scope self) -> Reservation<'scope, 'env> {
+        Reservation {
+            reference: unsafe { self.rc.acquire() },
+            extender: PhantomData,
+        }
+    }
+
+    /// Reserve a reference to the scope, unless the limit is reached.
+    pub fn try_reserve(&'scope self) -> Result<Reservation<'scope, 'env>, LimitReached> {
+        let reference = unsafe { self.rc.try_acquire() }.ok_or(LimitReached)?;
+        Ok(Reservation {
+            reference,
+            extender: PhantomData,
+        })
+    }
+
+    /// Reserve a reference to the scope, waiting without blocking while
+    /// the limit is reached.
+    pub async fn reserve_async(&'scope self) -> Reservation<'scope, 'env> {
+        Reservation {
+            reference: unsafe { self.rc.acquire_async() }.await,
+            extender: PhantomData,
+        }
+    }
+}
+
+/// Reference to the scope reserved for a single extension by
+/// [`Extender::reserve`], which doesn't block. Dropping it releases the
+/// reference.
+pub struct Reservation<'scope, 'env> {
+    reference: Reference,
+    extender: PhantomData<&'scope Extender<'scope, 'env>>,
+}
+
+impl<'scope, 'env> Reservation<'scope, 'env> {
+    pub(super) fn associate_reference<T>(self, inner: T) -> AssociateReference<T> {
+        AssociateReference {
+            inner,
+            _reference_guard: self.reference,
+        }
+    }
+}
+
+/// Error returned from [`Extender::try_reserve`] when the limit of
+/// [`lock_scope_bounded`](crate::lock_scope_bounded) is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitReached;
+
+impl fmt::Display for LimitReached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("limit of extended objects is reached")
+    }
+}
+
+impl core::error::Error for LimitReached {}
@@ -0,0 +1,91 @@
+use alloc::boxed::Box;
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task;
+
+use crate::Extender;
+
+use super::{AssociateReference, UnsafeAssertSync};
+
+/// Boxed closures and futures, allocated together with their reference
+/// to the scope. Unlike boxing the result of [`Extender::fn_`], it takes
+/// a single allocation and a single indirection per call.
+impl<'scope, 'env> Extender<'scope, 'env> {
+    pub fn boxed_fn_once<F, I, O>(&'scope self, f: F) -> Box<dyn FnOnce(I) -> O + Send + Sync>
+    where
+        F: FnOnce(I) -> O + Send + 'scope,
+    {
+        let f = unsafe { self.associate_reference(UnsafeAssertSync(f)) };
+        let f: Box<dyn FnOnce(I) -> O + Send + Sync + 'scope> = Box::new(move |i| {
+            let f = f;
+            f.inner.0(i)
+        });
+        unsafe {
+            mem::transmute::<
+                Box<dyn FnOnce(I) -> O + Send + Sync + 'scope>,
+                Box<dyn FnOnce(I) -> O + Send + Sync>,
+            >(f)
+        }
+    }
+
+    pub fn boxed_fn_mut<F, I, O>(&'scope self, f: F) -> Box<dyn FnMut(I) -> O + Send + Sync>
+    where
+        F: FnMut(I) -> O + Send + 'scope,
+    {
+        let mut f = unsafe { self.associate_reference(UnsafeAssertSync(f)) };
+        let f: Box<dyn FnMut(I) -> O + Send + Sync + 'scope> = Box::new(move |i| {
+            let f = &mut f;
+            f.inner.0(i)
+        });
+        unsafe {
+            mem::transmute::<
+                Box<dyn FnMut(I) -> O + Send + Sync + 'scope>,
+                Box<dyn FnMut(I) -> O + Send + Sync>,
+            >(f)
+        }
+    }
+
+    pub fn boxed_fn<F, I, O>(&'scope self, f: F) -> Box<dyn Fn(I) -> O + Send + Sync>
+    where
+        F: Fn(I) -> O + Send + Sync + 'scope,
+    {
+        let f = unsafe { self.associate_reference(f) };
+        let f: Box<dyn Fn(I) -> O + Send + Sync + 'scope> = Box::new(move |i| {
+            let f = &f;
+            (f.inner)(i)
+        });
+        unsafe {
+            mem::transmute::<
+                Box<dyn Fn(I) -> O + Send + Sync + 'scope>,
+                Box<dyn Fn(I) -> O + Send + Sync>,
+            >(f)
+        }
+    }
+
+    pub fn boxed_future<F>(
+        &'scope self,
+        f: F,
+    ) -> Pin<Box<dyn Future<Output = F::Output> + Send + Sync>>
+    where
+        F: Future + Send + 'scope,
+    {
+        let f = unsafe { self.associate_reference(UnsafeAssertSync(f)) };
+        let f: Pin<Box<dyn Future<Output = F::Output> + Send + Sync + 'scope>> = Box::pin(f);
+        unsafe {
+            mem::transmute::<
+                Pin<Box<dyn Future<Output = F::Output> + Send + Sync + 'scope>>,
+                Pin<Box<dyn Future<Output = F::Output> + Send + Sync>>,
+            >(f)
+        }
+    }
+}
+
+// It is sync since there's no way to poll it through a shared reference
+impl<F: Future> Future for AssociateReference<UnsafeAssertSync<F>> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        unsafe { self.map_unchecked_mut(|this| &mut this.inner.0) }.poll(cx)
+    }
+}
@@ -1,12 +1,18 @@
+use alloc::boxed::Box;
 use core::marker::PhantomData;
 use core::mem;
+use core::ops::{Deref, DerefMut};
 use core::ptr;
+use core::sync::atomic::{self, AtomicPtr, AtomicUsize, Ordering};
 
 use crate::Extender;
-use crate::pointer_like::erased_static::{fn_call, fn_call_mut, fn_call_once, fn_drop};
-use crate::pointer_like::{PointerDeref, PointerDerefMut, PointerIntoInner};
+use crate::pointer_like::erased_static::{
+    fn_call_once, fn_call_once_shared, fn_call_once_shared_or_ref, fn_drop,
+};
+use crate::pointer_like::{PointerDeref, PointerDerefMut, PointerIntoInner, PointerTryIntoInner};
 
-use super::{UnsafeAssertSend, UnsafeAssertSync};
+use super::bounded::Reservation;
+use super::{AssociateReference, ErasedPtr};
 
 impl<'scope, 'env> Extender<'scope, 'env> {
     pub fn fn_once<'extended, P, I, O>(
@@ -15,20 +21,71 @@ impl<'scope, 'env> Extender<'scope, 'env> {
     ) -> impl FnOnce(I) -> O + Send + Sync + 'extended
     where
         'extended: 'scope,
-        P: PointerIntoInner + Send,
+        P: PointerIntoInner + Send + 'scope,
         P::Pointee: FnOnce(I) -> O,
         I: Send + 'extended,
         O: Send + 'extended,
     {
-        let f = unsafe {
-            self.associate_reference(UnsafeAssertSync(UnsafeAssertSend(
-                extend_fn_once_unchecked(f),
-            )))
-        };
-        move |i| {
-            let f = f;
-            f.inner.0.0(i)
-        }
+        let f = ExtendedFnOnce::new(self, f).into_static();
+        move |i| f.call_once(i)
+    }
+
+    /// Same as [`Self::fn_once`], but accepts shared pointers like
+    /// [`Arc`](alloc::sync::Arc). Closure is moved out and called by value
+    /// by the last owner of it, like with
+    /// [`Arc::into_inner`](alloc::sync::Arc::into_inner). Calls of other
+    /// owners return `None` without calling it.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let report = String::from("done");
+    /// let f = Arc::new(move |()| report);
+    /// scope_lock::lock_scope(|e| {
+    ///     let first = e.fn_once_shared(f.clone());
+    ///     let last = e.fn_once_shared(f);
+    ///     assert_eq!(first(()), None);
+    ///     let handle = thread::spawn(move || last(()));
+    ///     assert_eq!(handle.join().unwrap().as_deref(), Some("done"));
+    /// });
+    /// ```
+    pub fn fn_once_shared<'extended, P, I, O>(
+        &'scope self,
+        f: P,
+    ) -> impl FnOnce(I) -> Option<O> + Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerTryIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(I) -> O,
+        I: Send + 'extended,
+        O: Send + 'extended,
+    {
+        let f = ExtendedFnOnce::new_shared(self, f).into_static();
+        move |i| f.call_once(i)
+    }
+
+    /// Same as [`Self::fn_once_shared`], but falls back to calling an
+    /// [`Fn`] closure by reference if the returned closure isn't the only
+    /// owner of it at the time of the call, so it is called every time.
+    ///
+    /// Ownership is checked like with
+    /// [`Arc::try_unwrap`](alloc::sync::Arc::try_unwrap) rather than
+    /// [`Arc::into_inner`](alloc::sync::Arc::into_inner), since the pointer
+    /// is needed back to call the closure by reference.
+    pub fn fn_once_shared_or_ref<'extended, P, I, O>(
+        &'scope self,
+        f: P,
+    ) -> impl FnOnce(I) -> O + Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerTryIntoInner + Send + 'scope,
+        P::Pointee: Fn(I) -> O + Sync,
+        I: Send + 'extended,
+        O: Send + 'extended,
+    {
+        let f = ExtendedFnOnce::new_shared_or_ref(self, f).into_static();
+        move |i| f.call_once(i)
     }
 
     pub fn fn_mut<'extended, P, I, O>(
@@ -37,55 +94,355 @@ impl<'scope, 'env> Extender<'scope, 'env> {
     ) -> impl FnMut(I) -> O + Send + Sync + 'extended
     where
         'extended: 'scope,
-        P: PointerDerefMut + Send,
+        P: PointerDerefMut + Send + 'scope,
         P::Pointee: FnMut(I) -> O,
         I: Send + 'extended,
         O: Send + 'extended,
     {
-        let mut f = unsafe {
-            self.associate_reference(UnsafeAssertSync(UnsafeAssertSend(extend_fn_mut_unchecked(
-                f,
-            ))))
-        };
-        move |i| {
-            let f = &mut f;
-            f.inner.0.0(i)
-        }
+        let mut f = ExtendedFnMut::new(self, f).into_static();
+        move |i| f(i)
     }
 
-    pub fn fn_<'extended, P, I, O>(&'scope self, f: P) -> impl Fn(I) -> O + Send + Sync + 'extended
+    /// Extend lifetime of a [`Fn`] closure. Returned closure is cheaply
+    /// [`Clone`]: every clone holds its own reference to the scope, and
+    /// the closure behind `f` is dropped together with the last clone.
+    pub fn fn_<'extended, P, I, O>(
+        &'scope self,
+        f: P,
+    ) -> impl Fn(I) -> O + Clone + Send + Sync + 'extended
     where
         'extended: 'scope,
-        P: PointerDeref + Send,
+        P: PointerDeref + Send + 'scope,
         P::Pointee: Fn(I) -> O + Sync,
         I: Send + 'extended,
         O: Send + 'extended,
     {
-        let f = unsafe {
-            self.associate_reference(UnsafeAssertSync(UnsafeAssertSend(extend_fn_unchecked(f))))
-        };
-        move |i| {
-            let f = &f;
-            f.inner.0.0(i)
-        }
+        let f = ExtendedFn::new(self, f).into_static();
+        move |i| f(i)
     }
 
+    /// Same as [`Self::fn_`], but doesn't require the closure to be
+    /// [`Sync`]. Returned closure isn't [`Clone`], since clones could be
+    /// sent to different threads and call the closure concurrently.
     pub fn fn_unsync<'extended, P, I, O>(&'scope self, f: P) -> impl Fn(I) -> O + Send + 'extended
     where
         'extended: 'scope,
-        P: PointerDeref + Send,
+        P: PointerDeref + Send + 'scope,
         P::Pointee: Fn(I) -> O,
         I: Send + 'extended,
         O: Send + 'extended,
     {
-        let f = unsafe { self.associate_reference(UnsafeAssertSend(extend_fn_unchecked(f))) };
-        move |i| {
-            let f = &f;
-            f.inner.0(i)
+        let f = ExtendedFnUnsync::new(self, f).into_static();
+        move |i| f(i)
+    }
+}
+
+impl<'scope, 'env> Reservation<'scope, 'env> {
+    /// Same as [`Extender::fn_once`], but uses the reserved reference.
+    pub fn fn_once<'extended, P, I, O>(self, f: P) -> impl FnOnce(I) -> O + Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(I) -> O,
+        I: Send + 'extended,
+        O: Send + 'extended,
+    {
+        let f = ExtendedFnOnce::<'static, I, O> {
+            inner: self.associate_reference(unsafe { ErasedFnOnce::new(f) }),
+            _scope: PhantomData,
+        };
+        move |i| f.call_once(i)
+    }
+
+    /// Same as [`Extender::fn_mut`], but uses the reserved reference.
+    pub fn fn_mut<'extended, P, I, O>(self, f: P) -> impl FnMut(I) -> O + Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerDerefMut + Send + 'scope,
+        P::Pointee: FnMut(I) -> O,
+        I: Send + 'extended,
+        O: Send + 'extended,
+    {
+        let mut f = ExtendedFnMut::<'static, I, O> {
+            inner: self.associate_reference(unsafe { ErasedFnMut::new(f) }),
+            _scope: PhantomData,
+        };
+        move |i| f(i)
+    }
+
+    /// Same as [`Extender::fn_`], but uses the reserved reference.
+    pub fn fn_<'extended, P, I, O>(self, f: P) -> impl Fn(I) -> O + Clone + Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerDeref + Send + 'scope,
+        P::Pointee: Fn(I) -> O + Sync,
+        I: Send + 'extended,
+        O: Send + 'extended,
+    {
+        let f = ExtendedFn::<'static, I, O> {
+            inner: self.associate_reference(unsafe { ErasedFn::new(f) }),
+            _scope: PhantomData,
+        };
+        move |i| f(i)
+    }
+}
+
+/// Extended [`FnOnce`] closure, a nameable counterpart of the one returned
+/// from [`Extender::fn_once`] to store in fields or enums. Call it with
+/// [`Self::call_once`], since implementing [`FnOnce`] and the rest of
+/// closure traits for custom types requires the unstable `fn_traits`
+/// feature.
+///
+/// It is bound to the lifetime of the scope, so it cannot escape it by
+/// accident. Use [`Self::into_static`] to pass it where `'static` is
+/// required.
+///
+/// # Deadlocks
+///
+/// Once `'static`, the closure could also be returned from the scope or
+/// stored somewhere outliving it, same as [`Extended`](crate::Extended).
+/// Since the scope waits for the closure to be dropped before it returns,
+/// that blocks forever:
+///
+/// ```no_run
+/// let data = vec![1, 2, 3];
+/// // never returns
+/// let _f = scope_lock::lock_scope(|e| {
+///     scope_lock::ExtendedFnOnce::new(e, Box::new(|()| data.len())).into_static()
+/// });
+/// ```
+pub struct ExtendedFnOnce<'scope, I, O> {
+    inner: AssociateReference<ErasedFnOnce<'static, I, O>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, I, O> ExtendedFnOnce<'scope, I, O> {
+    /// Same as [`Extender::fn_once`].
+    pub fn new<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(I) -> O,
+        I: Send,
+        O: Send,
+    {
+        ExtendedFnOnce {
+            inner: unsafe { extender.associate_reference(ErasedFnOnce::new(f)) },
+            _scope: PhantomData,
+        }
+    }
+
+    /// Same as [`Extender::fn_once_shared_or_ref`].
+    pub fn new_shared_or_ref<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerTryIntoInner + Send + 'scope,
+        P::Pointee: Fn(I) -> O + Sync,
+        I: Send,
+        O: Send,
+    {
+        ExtendedFnOnce {
+            inner: unsafe { extender.associate_reference(ErasedFnOnce::new_shared_or_ref(f)) },
+            _scope: PhantomData,
+        }
+    }
+
+    pub fn call_once(self, input: I) -> O {
+        self.inner.inner.call_once(input)
+    }
+
+    /// Erase the lifetime of the scope, like closures returned from
+    /// [`Extender::fn_once`] do. Returned closure should be dropped before
+    /// the end of the scope, see [deadlocks](Self#deadlocks).
+    pub fn into_static(self) -> ExtendedFnOnce<'static, I, O> {
+        ExtendedFnOnce {
+            inner: self.inner,
+            _scope: PhantomData,
+        }
+    }
+}
+
+impl<'scope, I, O> ExtendedFnOnce<'scope, I, Option<O>> {
+    /// Same as [`Extender::fn_once_shared`].
+    pub fn new_shared<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerTryIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(I) -> O,
+        I: Send,
+        O: Send,
+    {
+        ExtendedFnOnce {
+            inner: unsafe { extender.associate_reference(ErasedFnOnce::new_shared(f)) },
+            _scope: PhantomData,
+        }
+    }
+}
+
+unsafe impl<I: Send, O: Send> Send for ExtendedFnOnce<'_, I, O> {}
+// There's no way to interact with a reference to it
+unsafe impl<I, O> Sync for ExtendedFnOnce<'_, I, O> {}
+
+/// Extended [`FnMut`] closure, a nameable counterpart of the one returned
+/// from [`Extender::fn_mut`]. Call it through [`DerefMut`], since it
+/// cannot implement [`FnMut`] itself, see [`ExtendedFnOnce`].
+///
+/// Same as [`ExtendedFnOnce`], it is bound to the lifetime of the scope.
+pub struct ExtendedFnMut<'scope, I, O> {
+    inner: AssociateReference<ErasedFnMut<'static, I, O>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, I, O> ExtendedFnMut<'scope, I, O> {
+    /// Same as [`Extender::fn_mut`].
+    pub fn new<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerDerefMut + Send + 'scope,
+        P::Pointee: FnMut(I) -> O,
+        I: Send,
+        O: Send,
+    {
+        ExtendedFnMut {
+            inner: unsafe { extender.associate_reference(ErasedFnMut::new(f)) },
+            _scope: PhantomData,
+        }
+    }
+
+    /// Erase the lifetime of the scope, like closures returned from
+    /// [`Extender::fn_mut`] do. Returned closure should be dropped before
+    /// the end of the scope, see [deadlocks](ExtendedFnOnce#deadlocks).
+    pub fn into_static(self) -> ExtendedFnMut<'static, I, O> {
+        ExtendedFnMut {
+            inner: self.inner,
+            _scope: PhantomData,
+        }
+    }
+}
+
+impl<I, O> Deref for ExtendedFnMut<'_, I, O> {
+    type Target = dyn FnMut(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.inner.inner
+    }
+}
+
+impl<I, O> DerefMut for ExtendedFnMut<'_, I, O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.inner.inner
+    }
+}
+
+unsafe impl<I: Send, O: Send> Send for ExtendedFnMut<'_, I, O> {}
+// `FnMut` cannot be called through a shared reference
+unsafe impl<I, O> Sync for ExtendedFnMut<'_, I, O> {}
+
+/// Extended [`Fn`] closure, a nameable counterpart of the one returned
+/// from [`Extender::fn_`]. Call it through [`Deref`], since it cannot
+/// implement [`Fn`] itself, see [`ExtendedFnOnce`].
+///
+/// Same as [`ExtendedFnOnce`], it is bound to the lifetime of the scope.
+pub struct ExtendedFn<'scope, I, O> {
+    inner: AssociateReference<ErasedFn<'static, I, O>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, I, O> ExtendedFn<'scope, I, O> {
+    /// Same as [`Extender::fn_`].
+    pub fn new<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerDeref + Send + 'scope,
+        P::Pointee: Fn(I) -> O + Sync,
+        I: Send,
+        O: Send,
+    {
+        ExtendedFn {
+            inner: unsafe { extender.associate_reference(ErasedFn::new(f)) },
+            _scope: PhantomData,
+        }
+    }
+
+    /// Erase the lifetime of the scope, like closures returned from
+    /// [`Extender::fn_`] do. Returned closure and its clones should be
+    /// dropped before the end of the scope, see
+    /// [deadlocks](ExtendedFnOnce#deadlocks).
+    pub fn into_static(self) -> ExtendedFn<'static, I, O> {
+        ExtendedFn {
+            inner: self.inner,
+            _scope: PhantomData,
+        }
+    }
+}
+
+impl<I, O> Clone for ExtendedFn<'_, I, O> {
+    fn clone(&self) -> Self {
+        ExtendedFn {
+            inner: self.inner.clone(),
+            _scope: PhantomData,
         }
     }
 }
 
+impl<I, O> Deref for ExtendedFn<'_, I, O> {
+    type Target = dyn Fn(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.inner.inner
+    }
+}
+
+unsafe impl<I: Send, O: Send> Send for ExtendedFn<'_, I, O> {}
+unsafe impl<I: Send, O: Send> Sync for ExtendedFn<'_, I, O> {}
+
+/// Extended [`Fn`] closure, a nameable counterpart of the one returned
+/// from [`Extender::fn_unsync`]. Call it through [`Deref`], since it
+/// cannot implement [`Fn`] itself, see [`ExtendedFnOnce`].
+///
+/// Same as [`ExtendedFnOnce`], it is bound to the lifetime of the scope.
+pub struct ExtendedFnUnsync<'scope, I, O> {
+    inner: AssociateReference<ErasedFn<'static, I, O>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, I, O> ExtendedFnUnsync<'scope, I, O> {
+    /// Same as [`Extender::fn_unsync`].
+    pub fn new<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerDeref + Send + 'scope,
+        P::Pointee: Fn(I) -> O,
+        I: Send,
+        O: Send,
+    {
+        ExtendedFnUnsync {
+            inner: unsafe { extender.associate_reference(ErasedFn::new(f)) },
+            _scope: PhantomData,
+        }
+    }
+
+    /// Erase the lifetime of the scope, like closures returned from
+    /// [`Extender::fn_unsync`] do. Returned closure should be dropped before
+    /// the end of the scope, see [deadlocks](ExtendedFnOnce#deadlocks).
+    pub fn into_static(self) -> ExtendedFnUnsync<'static, I, O> {
+        ExtendedFnUnsync {
+            inner: self.inner,
+            _scope: PhantomData,
+        }
+    }
+}
+
+impl<I, O> Deref for ExtendedFnUnsync<'_, I, O> {
+    type Target = dyn Fn(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.inner.inner
+    }
+}
+
+unsafe impl<I: Send, O: Send> Send for ExtendedFnUnsync<'_, I, O> {}
+
+/// Erase lifetime of a [`FnOnce`] closure behind the pointer `f`.
+///
+/// # Safety
+///
+/// Returned closure must not be used or dropped after the lifetime of the
+/// original pointer `f` has ended.
 pub unsafe fn extend_fn_once_unchecked<'a, F, I, O>(f: F) -> impl FnOnce(I) -> O + 'a
 where
     F: PointerIntoInner,
@@ -93,17 +450,54 @@ where
     I: 'a,
     O: 'a,
 {
-    let f = unsafe {
-        ErasedFn {
-            ptr: ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()),
-            call: fn_call_once::<F, I, O>(),
-            drop: fn_drop::<F>(),
-            _marker: PhantomData,
-        }
-    };
+    let f = unsafe { ErasedFnOnce::new(f) };
+    move |i| f.call_once(i)
+}
+
+/// Erase lifetime of a [`FnOnce`] closure behind the shared pointer `f`,
+/// calling it by value if `f` is the last owner of it at the time of the
+/// call, or returning `None` otherwise.
+///
+/// # Safety
+///
+/// Returned closure must not be used or dropped after the lifetime of the
+/// original pointer `f` has ended.
+pub unsafe fn extend_fn_once_shared_unchecked<'a, F, I, O>(f: F) -> impl FnOnce(I) -> Option<O> + 'a
+where
+    F: PointerTryIntoInner,
+    F::Pointee: FnOnce(I) -> O,
+    I: 'a,
+    O: 'a,
+{
+    let f = unsafe { ErasedFnOnce::new_shared(f) };
     move |i| f.call_once(i)
 }
 
+/// Erase lifetime of a [`Fn`] closure behind the shared pointer `f`,
+/// calling it by value if `f` is the only owner of it at the time of the
+/// call, or by reference otherwise.
+///
+/// # Safety
+///
+/// Returned closure must not be used or dropped after the lifetime of the
+/// original pointer `f` has ended.
+pub unsafe fn extend_fn_once_shared_or_ref_unchecked<'a, F, I, O>(f: F) -> impl FnOnce(I) -> O + 'a
+where
+    F: PointerTryIntoInner,
+    F::Pointee: Fn(I) -> O,
+    I: 'a,
+    O: 'a,
+{
+    let f = unsafe { ErasedFnOnce::new_shared_or_ref(f) };
+    move |i| f.call_once(i)
+}
+
+/// Erase lifetime of a [`FnMut`] closure behind the pointer `f`.
+///
+/// # Safety
+///
+/// Returned closure must not be used or dropped after the lifetime of the
+/// original pointer `f` has ended.
 pub unsafe fn extend_fn_mut_unchecked<'a, F, I, O>(f: F) -> impl FnMut(I) -> O + 'a
 where
     F: PointerDerefMut,
@@ -111,67 +505,253 @@ where
     I: 'a,
     O: 'a,
 {
-    let mut f = unsafe {
-        ErasedFn {
-            ptr: ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()),
-            call: fn_call_mut::<F, I, O>(),
-            drop: fn_drop::<F>(),
-            _marker: PhantomData,
-        }
-    };
-    move |i| f.call_mut(i)
+    let mut f = unsafe { ErasedFnMut::new(f) };
+    move |i| f(i)
 }
 
-pub unsafe fn extend_fn_unchecked<'a, F, I, O>(f: F) -> impl Fn(I) -> O + 'a
+/// Erase lifetime of a [`Fn`] closure behind the pointer `f`. Clones of
+/// the returned closure share the pointer, which is dropped together
+/// with the last clone.
+///
+/// # Safety
+///
+/// Returned closure and its clones must not be used or dropped after the
+/// lifetime of the original pointer `f` has ended.
+pub unsafe fn extend_fn_unchecked<'a, F, I, O>(f: F) -> impl Fn(I) -> O + Clone + 'a
 where
     F: PointerDeref,
     F::Pointee: Fn(I) -> O,
     I: 'a,
     O: 'a,
 {
-    let f = unsafe {
-        ErasedFn {
-            ptr: ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()),
-            call: fn_call::<F, I, O>(),
-            drop: fn_drop::<F>(),
-            _marker: PhantomData,
-        }
-    };
-    move |i| f.call(i)
+    let f = unsafe { ErasedFn::new(f) };
+    move |i| f(i)
 }
 
-struct ErasedFn<C, I, O, D: Fn(*mut ())> {
+/// Lifetime-erased [`FnOnce`] closure, a nameable counterpart of the one
+/// returned from [`extend_fn_once_unchecked`].
+pub struct ErasedFnOnce<'a, I, O> {
     ptr: ptr::NonNull<()>,
-    drop: D,
-    call: C,
-    _marker: PhantomData<fn(I) -> O>,
+    call_once: unsafe fn(*mut (), I) -> O,
+    drop: unsafe fn(*mut ()),
+    _marker: PhantomData<&'a ()>,
 }
 
-impl<C, I, O, D: Fn(*mut ())> ErasedFn<C, I, O, D>
-where
-    C: Fn(*mut (), I) -> O,
-{
-    fn call_once(self, input: I) -> O {
+impl<'a, I, O> ErasedFnOnce<'a, I, O> {
+    /// Same as [`extend_fn_once_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`extend_fn_once_unchecked`].
+    pub unsafe fn new<F>(f: F) -> Self
+    where
+        F: PointerIntoInner,
+        F::Pointee: FnOnce(I) -> O,
+    {
+        ErasedFnOnce {
+            ptr: unsafe { ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()) },
+            call_once: |ptr, input| unsafe { fn_call_once::<F, I, O>()(ptr, input) },
+            drop: |ptr| unsafe { fn_drop::<F>()(ptr) },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Same as [`extend_fn_once_shared_or_ref_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`extend_fn_once_shared_or_ref_unchecked`].
+    pub unsafe fn new_shared_or_ref<F>(f: F) -> Self
+    where
+        F: PointerTryIntoInner,
+        F::Pointee: Fn(I) -> O,
+    {
+        ErasedFnOnce {
+            ptr: unsafe { ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()) },
+            call_once: |ptr, input| unsafe { fn_call_once_shared_or_ref::<F, I, O>()(ptr, input) },
+            drop: |ptr| unsafe { fn_drop::<F>()(ptr) },
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn call_once(self, input: I) -> O {
         let this = mem::ManuallyDrop::new(self);
-        (this.call)(this.ptr.as_ptr(), input)
+        unsafe { (this.call_once)(this.ptr.as_ptr(), input) }
     }
+}
 
-    fn call_mut(&mut self, input: I) -> O {
-        (self.call)(self.ptr.as_ptr(), input)
+impl<'a, I, O> ErasedFnOnce<'a, I, Option<O>> {
+    /// Same as [`extend_fn_once_shared_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`extend_fn_once_shared_unchecked`].
+    pub unsafe fn new_shared<F>(f: F) -> Self
+    where
+        F: PointerTryIntoInner,
+        F::Pointee: FnOnce(I) -> O,
+    {
+        ErasedFnOnce {
+            ptr: unsafe { ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()) },
+            call_once: |ptr, input| unsafe { fn_call_once_shared::<F, I, O>()(ptr, input) },
+            drop: |ptr| unsafe { fn_drop::<F>()(ptr) },
+            _marker: PhantomData,
+        }
     }
 }
 
-impl<C, I, O, D: Fn(*mut ())> ErasedFn<C, I, O, D>
-where
-    C: Fn(*const (), I) -> O,
-{
-    fn call(&self, input: I) -> O {
-        (self.call)(self.ptr.as_ptr(), input)
+impl<I, O> Drop for ErasedFnOnce<'_, I, O> {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.ptr.as_ptr()) }
+    }
+}
+
+/// Lifetime-erased [`FnMut`] closure, a nameable counterpart of the one
+/// returned from [`extend_fn_mut_unchecked`]. Call it through
+/// [`DerefMut`].
+pub struct ErasedFnMut<'a, I, O> {
+    inner: ErasedPtr<dyn FnMut(I) -> O + 'a>,
+}
+
+impl<'a, I, O> ErasedFnMut<'a, I, O> {
+    /// Same as [`extend_fn_mut_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`extend_fn_mut_unchecked`].
+    pub unsafe fn new<F>(f: F) -> Self
+    where
+        F: PointerDerefMut,
+        F::Pointee: FnMut(I) -> O,
+    {
+        let ptr = unsafe {
+            mem::transmute::<*mut (dyn FnMut(I) -> O + '_), *mut (dyn FnMut(I) -> O + 'a)>(
+                f.into_ptr(),
+            )
+        };
+        ErasedFnMut {
+            inner: unsafe { ErasedPtr::new::<F>(ptr) },
+        }
+    }
+}
+
+impl<'a, I, O> Deref for ErasedFnMut<'a, I, O> {
+    type Target = dyn FnMut(I) -> O + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.inner.ptr.as_ref() }
     }
 }
 
-impl<C, I, O, D: Fn(*mut ())> Drop for ErasedFn<C, I, O, D> {
+impl<I, O> DerefMut for ErasedFnMut<'_, I, O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.inner.ptr.as_mut() }
+    }
+}
+
+/// Lifetime-erased [`Fn`] closure, a nameable counterpart of the one
+/// returned from [`extend_fn_unchecked`]. Call it through [`Deref`].
+pub struct ErasedFn<'a, I, O> {
+    inner: SharedErased<ErasedPtr<dyn Fn(I) -> O + 'a>>,
+}
+
+impl<'a, I, O> ErasedFn<'a, I, O> {
+    /// Same as [`extend_fn_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`extend_fn_unchecked`].
+    pub unsafe fn new<F>(f: F) -> Self
+    where
+        F: PointerDeref,
+        F::Pointee: Fn(I) -> O,
+    {
+        let ptr = unsafe {
+            mem::transmute::<*mut (dyn Fn(I) -> O + '_), *mut (dyn Fn(I) -> O + 'a)>(f.into_ptr())
+        };
+        ErasedFn {
+            inner: SharedErased::new(unsafe { ErasedPtr::new::<F>(ptr) }),
+        }
+    }
+}
+
+impl<I, O> Clone for ErasedFn<'_, I, O> {
+    fn clone(&self) -> Self {
+        ErasedFn {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, I, O> Deref for ErasedFn<'a, I, O> {
+    type Target = dyn Fn(I) -> O + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.inner.inner.ptr.as_ref() }
+    }
+}
+
+/// Shared ownership over [`ErasedPtr`], which allocates its counter only
+/// once the first clone is made.
+struct SharedErased<T> {
+    inner: mem::ManuallyDrop<T>,
+    // null until the first clone
+    count: AtomicPtr<AtomicUsize>,
+}
+
+impl<T> SharedErased<T> {
+    fn new(inner: T) -> Self {
+        SharedErased {
+            inner: mem::ManuallyDrop::new(inner),
+            count: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    fn count(&self) -> &AtomicUsize {
+        let mut count = self.count.load(Ordering::Acquire);
+        if count.is_null() {
+            let new = Box::into_raw(Box::new(AtomicUsize::new(1)));
+            count = match self.count.compare_exchange(
+                ptr::null_mut(),
+                new,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => new,
+                Err(other) => {
+                    drop(unsafe { Box::from_raw(new) });
+                    other
+                }
+            };
+        }
+        unsafe { &*count }
+    }
+}
+
+impl<T> Clone for SharedErased<T> {
+    fn clone(&self) -> Self {
+        let count = self.count();
+        // Same as `Arc`, abort instead of overflowing
+        if count.fetch_add(1, Ordering::Relaxed) > isize::MAX as usize {
+            std::process::abort();
+        }
+        SharedErased {
+            inner: unsafe { ptr::read(&self.inner) },
+            count: AtomicPtr::new(count as *const AtomicUsize as *mut AtomicUsize),
+        }
+    }
+}
+
+impl<T> Drop for SharedErased<T> {
     fn drop(&mut self) {
-        (self.drop)(self.ptr.as_ptr())
+        let count = *self.count.get_mut();
+        if !count.is_null() {
+            if unsafe { (*count).fetch_sub(1, Ordering::Release) } != 1 {
+                return;
+            }
+            atomic::fence(Ordering::Acquire);
+            drop(unsafe { Box::from_raw(count) });
+        }
+        unsafe { mem::ManuallyDrop::drop(&mut self.inner) }
     }
 }
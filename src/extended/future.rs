@@ -1,16 +1,16 @@
 use core::future::Future;
 use core::marker::PhantomData;
+use core::mem;
 use core::pin::Pin;
-use core::ptr;
 use core::task;
 
 use crate::Extender;
-use crate::extended::sync::Reference;
 use crate::pointer_like::PointerPinUnforgotten;
-use crate::pointer_like::erased_static::{fn_drop, fn_poll_unforgotten};
+
+use super::bounded::Reservation;
+use super::{AssociateReference, ErasedPtr};
 
 impl<'scope, 'env> Extender<'scope, 'env> {
-    // TODO: pointer width extended value like extend_future_box
     pub fn future<'extended, P, O>(
         &'scope self,
         f: P,
@@ -21,90 +21,122 @@ impl<'scope, 'env> Extender<'scope, 'env> {
         P::Pointee: Future<Output = O>,
         O: Send + 'extended,
     {
-        let reference_guard = unsafe { self.rc.acquire() };
+        ExtendedFuture::new(self, f).into_static()
+    }
+}
 
-        struct Fut<T> {
-            inner: T,
-            // drop last
-            _reference_guard: Reference,
+impl<'scope, 'env> Reservation<'scope, 'env> {
+    /// Same as [`Extender::future`], but uses the reserved reference.
+    pub fn future<'extended, P, O>(self, f: P) -> impl Future<Output = O> + Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerPinUnforgotten + Send + 'scope,
+        P::Pointee: Future<Output = O>,
+        O: Send + 'extended,
+    {
+        ExtendedFuture::<'static, O> {
+            inner: self.associate_reference(unsafe { ErasedFuture::new(f) }),
+            _scope: PhantomData,
         }
-        unsafe impl<T> Send for Fut<T> {}
-        unsafe impl<T> Sync for Fut<T> {}
-        impl<T: Future> Future for Fut<T> {
-            type Output = T::Output;
+    }
+}
 
-            fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
-                unsafe { Pin::new_unchecked(&mut self.get_unchecked_mut().inner).poll(cx) }
-            }
-        }
+/// Extended future, a nameable counterpart of the one returned from
+/// [`Extender::future`] to store in fields or enums.
+///
+/// Same as [`ExtendedFnOnce`](crate::ExtendedFnOnce), it is bound to the
+/// lifetime of the scope.
+pub struct ExtendedFuture<'scope, O> {
+    inner: AssociateReference<ErasedFuture<'static, O>>,
+    _scope: PhantomData<&'scope ()>,
+}
 
-        // It is sync since there's no way to interact with a reference to returned type
-        Fut {
-            inner: unsafe { extend_future_unchecked(f) },
-            _reference_guard: reference_guard,
+impl<'scope, O> ExtendedFuture<'scope, O> {
+    /// Same as [`Extender::future`].
+    pub fn new<P>(extender: &'scope Extender<'scope, '_>, f: P) -> Self
+    where
+        P: PointerPinUnforgotten + Send + 'scope,
+        P::Pointee: Future<Output = O>,
+        O: Send,
+    {
+        ExtendedFuture {
+            inner: unsafe { extender.associate_reference(ErasedFuture::new(f)) },
+            _scope: PhantomData,
         }
     }
-}
 
-pub unsafe fn extend_future_unchecked<'a, F, O>(f: F) -> impl Future<Output = O> + 'a
-where
-    F: PointerPinUnforgotten,
-    F::Pointee: Future<Output = O>,
-    O: 'a,
-{
-    unsafe {
-        ErasedFuture {
-            ptr: ptr::NonNull::new_unchecked(f.into_ptr() as *mut ()),
-            poll: fn_poll_unforgotten::<F, O>(),
-            drop: fn_drop::<F>(),
-            _marker: PhantomData,
+    /// Erase the lifetime of the scope, like futures returned from
+    /// [`Extender::future`] do. Returned future should be dropped before
+    /// the end of the scope, see
+    /// [deadlocks](crate::ExtendedFnOnce#deadlocks).
+    pub fn into_static(self) -> ExtendedFuture<'static, O> {
+        ExtendedFuture {
+            inner: self.inner,
+            _scope: PhantomData,
         }
     }
 }
 
-struct ErasedFuture<P, O, D: Fn(*mut ())> {
-    ptr: ptr::NonNull<()>,
-    drop: D,
-    poll: P,
-    _marker: PhantomData<fn() -> task::Poll<O>>,
-}
-
-impl<P, O, D: Fn(*mut ())> Future for ErasedFuture<P, O, D>
-where
-    P: Fn(*mut (), &mut task::Context<'_>) -> task::Poll<O>,
-{
+impl<O> Future for ExtendedFuture<'_, O> {
     type Output = O;
 
     fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
-        (self.poll)(self.ptr.as_ptr(), cx)
+        Pin::new(&mut self.get_mut().inner.inner).poll(cx)
     }
 }
 
-impl<P, O, D: Fn(*mut ())> Drop for ErasedFuture<P, O, D> {
-    fn drop(&mut self) {
-        (self.drop)(self.ptr.as_ptr())
-    }
+unsafe impl<O: Send> Send for ExtendedFuture<'_, O> {}
+// It is sync since there's no way to interact with a reference to it
+unsafe impl<O> Sync for ExtendedFuture<'_, O> {}
+
+/// Erase lifetime of a future behind the pointer `f`.
+///
+/// # Safety
+///
+/// Returned future must not be polled or dropped after the lifetime of the
+/// original pointer `f` has ended.
+pub unsafe fn extend_future_unchecked<'a, F, O>(f: F) -> impl Future<Output = O> + 'a
+where
+    F: PointerPinUnforgotten,
+    F::Pointee: Future<Output = O>,
+    O: 'a,
+{
+    unsafe { ErasedFuture::new(f) }
 }
 
-pub mod legacy {
-    use core::future::Future;
-    use core::pin::Pin;
-    use core::ptr;
-    use core::task;
+/// Lifetime-erased future, a nameable counterpart of the one returned from
+/// [`extend_future_unchecked`].
+pub struct ErasedFuture<'a, O> {
+    inner: ErasedPtr<dyn Future<Output = O> + 'a>,
+}
 
-    #[doc(hidden)]
-    pub struct ExtendedFuture<O> {
-        // TODO: Could make a single dynamically sized struct
-        pub(crate) func: ptr::NonNull<dyn Future<Output = O> + Send>,
+impl<'a, O> ErasedFuture<'a, O> {
+    /// Same as [`extend_future_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`extend_future_unchecked`].
+    pub unsafe fn new<F>(f: F) -> Self
+    where
+        F: PointerPinUnforgotten,
+        F::Pointee: Future<Output = O>,
+    {
+        let ptr = unsafe {
+            mem::transmute::<*mut (dyn Future<Output = O> + '_), *mut (dyn Future<Output = O> + 'a)>(
+                f.into_ptr(),
+            )
+        };
+        ErasedFuture {
+            inner: unsafe { ErasedPtr::new::<F>(ptr) },
+        }
     }
+}
 
-    impl<O> Future for ExtendedFuture<O> {
-        type Output = O;
+impl<O> Future for ErasedFuture<'_, O> {
+    type Output = O;
 
-        fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
-            unsafe { Pin::new_unchecked(self.get_unchecked_mut().func.as_mut()) }.poll(cx)
-        }
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        // Pointee is pinned by the original pointer
+        unsafe { Pin::new_unchecked(self.get_mut().inner.ptr.as_mut()) }.poll(cx)
     }
-
-    unsafe impl<O> Send for ExtendedFuture<O> where O: Send {}
 }
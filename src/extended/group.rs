@@ -0,0 +1,87 @@
+use core::ops::Deref;
+
+use crate::Extender;
+
+use super::sync::ReferenceCounter;
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Run `group` with a child group of this scope and wait until every
+    /// object extended through the group is dropped, allowing phased
+    /// work within a single scope:
+    ///
+    /// ```
+    /// use std::thread;
+    ///
+    /// let mut data = vec![1, 2, 3];
+    /// scope_lock::lock_scope(|e| {
+    ///     let mut sum = 0;
+    ///     e.group(|g| {
+    ///         let sum = g.exclusive(&mut sum);
+    ///         let data = g.shared(&data);
+    ///         thread::spawn(move || {
+    ///             let mut sum = sum;
+    ///             *sum = data.iter().sum();
+    ///         });
+    ///     });
+    ///     // borrows of the group have ended
+    ///     data.push(sum);
+    ///     e.group(|g| {
+    ///         let data = g.shared(&data);
+    ///         thread::spawn(move || assert_eq!(data[3], 6));
+    ///     });
+    /// });
+    /// ```
+    ///
+    /// Unlike with [`Self::nested`], objects borrowed only within the
+    /// scope could be extended through the group, since its `'env` is
+    /// independent of `'scope`. The group holds a reference to this scope
+    /// until it ends, so objects extended through the group count toward
+    /// this scope as well.
+    ///
+    /// The group is only lent to `group`, and never returned by value
+    /// with something like `Group::wait(self)` to end it, because it
+    /// could be leaked with [`mem::forget`](core::mem::forget) instead,
+    /// ending its borrows while extended objects are still using them.
+    pub fn group<'group_env, F, T>(&'scope self, group: F) -> T
+    where
+        F: for<'group> FnOnce(&'group Group<'group, 'group_env>) -> T,
+    {
+        let _parent_reference = unsafe { self.rc.acquire_unbounded() };
+        let rc = ReferenceCounter::new();
+        let g = Group {
+            extender: Extender::new(&rc),
+        };
+        let _guard = g.extender.guard();
+        group(&g)
+    }
+}
+
+/// Child group of a scope passed into [`Extender::group`]. Extend objects
+/// through [`Deref`] into [`Extender`].
+pub struct Group<'group, 'env> {
+    extender: Extender<'group, 'env>,
+}
+
+impl<'group, 'env> Group<'group, 'env> {
+    /// Block until every object extended through the group so far is
+    /// dropped. Objects borrowed by the group stay borrowed until the end
+    /// of [`Extender::group`] anyway, but this allows to synchronize with
+    /// extended objects, like with [`std::thread::JoinHandle::join`], or
+    /// to limit the number of them alive.
+    ///
+    /// Weak extensions of the group created so far are revoked first.
+    /// Those created after the wait stay usable until the next one, or
+    /// the end of the group.
+    pub fn wait(&self) {
+        self.extender.rc.revoke_registered();
+        self.extender.rc.block();
+    }
+}
+
+impl<'group, 'env> Deref for Group<'group, 'env> {
+    type Target = Extender<'group, 'env>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.extender
+    }
+}
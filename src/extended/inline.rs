@@ -0,0 +1,195 @@
+use core::cell::UnsafeCell;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+use crate::Extender;
+use crate::extended::sync::Reference;
+
+/// Closures stored inline, without allocation or a caller-provided
+/// slot. Closure must fit into `N` machine words and must not be aligned
+/// more than a machine word, which is checked at compile time (during
+/// code generation, so `cargo check` won't catch it):
+///
+/// ```
+/// let (a, b) = (1, 2);
+/// scope_lock::lock_scope(|e| {
+///     let f: scope_lock::InlineFn<(), i32, 2> = e.fn_inline(|()| a + b);
+///     std::thread::spawn(move || assert_eq!(f(()), 3));
+/// });
+/// ```
+///
+/// ```compile_fail
+/// let (a, b, c) = (1, 2, 3);
+/// scope_lock::lock_scope(|e| {
+///     let f: scope_lock::InlineFn<(), i32, 2> = e.fn_inline(|()| a + b + c);
+/// });
+/// ```
+impl<'scope, 'env> Extender<'scope, 'env> {
+    pub fn fn_once_inline<F, I, O, const N: usize>(&'scope self, f: F) -> InlineFnOnce<I, O, N>
+    where
+        F: FnOnce(I) -> O + Send + 'scope,
+        I: Send,
+        O: Send,
+    {
+        InlineFnOnce {
+            buf: Buffer::new(f),
+            call_once: |ptr, input| unsafe { ptr::read(ptr as *mut F)(input) },
+            drop: |ptr| unsafe { ptr::drop_in_place(ptr as *mut F) },
+            _reference_guard: unsafe { self.rc.acquire() },
+        }
+    }
+
+    pub fn fn_mut_inline<F, I, O, const N: usize>(&'scope self, f: F) -> InlineFnMut<I, O, N>
+    where
+        F: FnMut(I) -> O + Send + 'scope,
+        I: Send,
+        O: Send,
+    {
+        InlineFnMut {
+            inner: Inline {
+                buf: Buffer::new(f),
+                to_dyn: |ptr| unsafe {
+                    mem::transmute::<*mut (dyn FnMut(I) -> O + '_), *mut dyn FnMut(I) -> O>(
+                        ptr as *mut F,
+                    )
+                },
+            },
+            _reference_guard: unsafe { self.rc.acquire() },
+        }
+    }
+
+    pub fn fn_inline<F, I, O, const N: usize>(&'scope self, f: F) -> InlineFn<I, O, N>
+    where
+        F: Fn(I) -> O + Send + Sync + 'scope,
+        I: Send,
+        O: Send,
+    {
+        InlineFn {
+            inner: Inline {
+                buf: Buffer::new(f),
+                to_dyn: |ptr| unsafe {
+                    mem::transmute::<*mut (dyn Fn(I) -> O + '_), *mut dyn Fn(I) -> O>(ptr as *mut F)
+                },
+            },
+            _reference_guard: unsafe { self.rc.acquire() },
+        }
+    }
+}
+
+/// Extended [`FnOnce`] closure stored inline, returned from
+/// [`Extender::fn_once_inline`].
+pub struct InlineFnOnce<I, O, const N: usize = 4> {
+    buf: Buffer<N>,
+    call_once: unsafe fn(*mut (), I) -> O,
+    drop: unsafe fn(*mut ()),
+    // drop last
+    _reference_guard: Reference,
+}
+
+impl<I, O, const N: usize> InlineFnOnce<I, O, N> {
+    pub fn call_once(self, input: I) -> O {
+        let this = mem::ManuallyDrop::new(self);
+        let _reference_guard = unsafe { ptr::read(&this._reference_guard) };
+        unsafe { (this.call_once)(this.buf.as_mut_ptr(), input) }
+    }
+}
+
+impl<I, O, const N: usize> Drop for InlineFnOnce<I, O, N> {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.buf.as_mut_ptr()) }
+    }
+}
+
+unsafe impl<I: Send, O: Send, const N: usize> Send for InlineFnOnce<I, O, N> {}
+// There's no way to interact with a reference to it
+unsafe impl<I, O, const N: usize> Sync for InlineFnOnce<I, O, N> {}
+
+/// Extended [`FnMut`] closure stored inline, returned from
+/// [`Extender::fn_mut_inline`]. Call it through [`DerefMut`].
+pub struct InlineFnMut<I, O, const N: usize = 4> {
+    inner: Inline<dyn FnMut(I) -> O, N>,
+    // drop last
+    _reference_guard: Reference,
+}
+
+impl<I, O, const N: usize> Deref for InlineFnMut<I, O, N> {
+    type Target = dyn FnMut(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.inner.as_ptr() }
+    }
+}
+
+impl<I, O, const N: usize> DerefMut for InlineFnMut<I, O, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.inner.as_ptr() }
+    }
+}
+
+unsafe impl<I: Send, O: Send, const N: usize> Send for InlineFnMut<I, O, N> {}
+// `FnMut` cannot be called through a shared reference
+unsafe impl<I, O, const N: usize> Sync for InlineFnMut<I, O, N> {}
+
+/// Extended [`Fn`] closure stored inline, returned from
+/// [`Extender::fn_inline`]. Call it through [`Deref`].
+pub struct InlineFn<I, O, const N: usize = 4> {
+    inner: Inline<dyn Fn(I) -> O, N>,
+    // drop last
+    _reference_guard: Reference,
+}
+
+impl<I, O, const N: usize> Deref for InlineFn<I, O, N> {
+    type Target = dyn Fn(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.inner.as_ptr() }
+    }
+}
+
+unsafe impl<I: Send, O: Send, const N: usize> Send for InlineFn<I, O, N> {}
+unsafe impl<I: Send, O: Send, const N: usize> Sync for InlineFn<I, O, N> {}
+
+/// Inline buffer of `N` machine words.
+struct Buffer<const N: usize>(UnsafeCell<[MaybeUninit<usize>; N]>);
+
+impl<const N: usize> Buffer<N> {
+    fn new<F>(f: F) -> Self {
+        const {
+            assert!(
+                mem::size_of::<F>() <= mem::size_of::<[usize; N]>(),
+                "closure doesn't fit into the inline buffer"
+            );
+            assert!(
+                mem::align_of::<F>() <= mem::align_of::<usize>(),
+                "closure is aligned more than the inline buffer"
+            );
+        }
+        let buf = Buffer(UnsafeCell::new([MaybeUninit::uninit(); N]));
+        unsafe { ptr::write(buf.as_mut_ptr() as *mut F, f) };
+        buf
+    }
+
+    fn as_mut_ptr(&self) -> *mut () {
+        self.0.get() as *mut ()
+    }
+}
+
+/// Type-erased value within a [`Buffer`], accessed through a trait
+/// object.
+struct Inline<T: ?Sized, const N: usize> {
+    buf: Buffer<N>,
+    to_dyn: unsafe fn(*mut ()) -> *mut T,
+}
+
+impl<T: ?Sized, const N: usize> Inline<T, N> {
+    fn as_ptr(&self) -> *mut T {
+        unsafe { (self.to_dyn)(self.buf.as_mut_ptr()) }
+    }
+}
+
+impl<T: ?Sized, const N: usize> Drop for Inline<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_ptr()) }
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::boxed::Box;
+use std::sync::mpsc;
+
+use crate::pointer_like::PointerIntoInner;
+use crate::{Extender, SlotPool};
+
+/// Job accepted by a [`JobQueue`].
+pub type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Queue of `'static` jobs, like a channel feeding a pool of worker
+/// threads.
+pub trait JobQueue {
+    type Error;
+
+    fn push(&self, job: Job) -> Result<(), Self::Error>;
+}
+
+impl JobQueue for mpsc::Sender<Job> {
+    type Error = mpsc::SendError<Job>;
+
+    fn push(&self, job: Job) -> Result<(), Self::Error> {
+        self.send(job)
+    }
+}
+
+impl JobQueue for mpsc::SyncSender<Job> {
+    type Error = mpsc::SendError<Job>;
+
+    fn push(&self, job: Job) -> Result<(), Self::Error> {
+        self.send(job)
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl JobQueue for crossbeam_channel::Sender<Job> {
+    type Error = crossbeam_channel::SendError<Job>;
+
+    fn push(&self, job: Job) -> Result<(), Self::Error> {
+        self.send(job)
+    }
+}
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Adapt a queue of `'static` jobs to accept extended closures.
+    pub fn job_queue<Q: JobQueue>(&'scope self, queue: Q) -> ExtendedSender<'scope, 'env, Q> {
+        ExtendedSender {
+            extender: self,
+            queue,
+        }
+    }
+}
+
+/// Sender of extended closures into a [`JobQueue`], returned from
+/// [`Extender::job_queue`]. Every sent closure is boxed into a [`Job`],
+/// since that's what the queue accepts. With
+/// [`ExtendedSender::send_pooled`], the boxed job only points into a slot
+/// of a [`SlotPool`] holding the closure itself, which is reused once
+/// the job is run or dropped.
+///
+/// ```
+/// use std::sync::mpsc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let (sender, receiver) = mpsc::channel::<scope_lock::Job>();
+/// let worker = std::thread::spawn(move || receiver.into_iter().for_each(|job| job()));
+///
+/// let sum = AtomicUsize::new(0);
+/// scope_lock::lock_scope(|e| {
+///     let sender = e.job_queue(sender);
+///     for i in 1..=4 {
+///         let sum = &sum;
+///         sender
+///             .send(Box::new(move |()| {
+///                 sum.fetch_add(i, Ordering::Relaxed);
+///             }))
+///             .unwrap();
+///     }
+/// });
+///
+/// assert_eq!(sum.into_inner(), 10);
+/// worker.join().unwrap();
+/// ```
+pub struct ExtendedSender<'scope, 'env, Q> {
+    extender: &'scope Extender<'scope, 'env>,
+    queue: Q,
+}
+
+impl<'scope, 'env, Q: JobQueue> ExtendedSender<'scope, 'env, Q> {
+    /// Extend closure and push it into the queue. If queue returns an
+    /// error, the job is dropped with it.
+    pub fn send<P>(&self, f: P) -> Result<(), Q::Error>
+    where
+        P: PointerIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(()),
+    {
+        let f = self.extender.fn_once(f);
+        self.queue.push(Box::new(move || f(())))
+    }
+
+    /// Same as [`Self::send`], but moves the closure into a free slot of
+    /// `pool` instead of taking a pointer to it. Falls back to boxing the
+    /// closure if every slot is occupied.
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let (sender, receiver) = mpsc::channel::<scope_lock::Job>();
+    /// let worker = std::thread::spawn(move || receiver.into_iter().for_each(|job| job()));
+    ///
+    /// let sum = AtomicUsize::new(0);
+    /// let pool = scope_lock::SlotPool::<_, 4>::new();
+    /// scope_lock::lock_scope(|e| {
+    ///     let sender = e.job_queue(sender);
+    ///     for i in 1..=16 {
+    ///         let sum = &sum;
+    ///         sender
+    ///             .send_pooled(&pool, move |()| {
+    ///                 sum.fetch_add(i, Ordering::Relaxed);
+    ///             })
+    ///             .unwrap();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(sum.into_inner(), 136);
+    /// worker.join().unwrap();
+    /// ```
+    pub fn send_pooled<F, const N: usize>(
+        &self,
+        pool: &'scope SlotPool<F, N>,
+        f: F,
+    ) -> Result<(), Q::Error>
+    where
+        F: FnOnce(()) + Send + 'scope,
+    {
+        match pool.try_insert(f) {
+            Ok(f) => self.send(f),
+            Err(f) => self.send(Box::new(f)),
+        }
+    }
+
+    pub fn queue(&self) -> &Q {
+        &self.queue
+    }
+
+    pub fn into_queue(self) -> Q {
+        self.queue
+    }
+}
@@ -0,0 +1,38 @@
+use core::ptr;
+
+use crate::Extender;
+use crate::pointer_like::PointerLike;
+use crate::pointer_like::erased_static::fn_drop;
+
+use super::{UnsafeAssertSend, UnsafeAssertSync};
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Extend lifetime of an object, which only needs to be dropped, like
+    /// a guard. Object is dropped together with the returned token, which
+    /// is guaranteed to happen before the end of the scope.
+    pub fn keep_alive<'extended, P>(&'scope self, p: P) -> impl Send + Sync + 'extended
+    where
+        'extended: 'scope,
+        P: PointerLike + Send + 'scope,
+    {
+        let inner = unsafe {
+            ErasedDrop {
+                ptr: ptr::NonNull::new_unchecked(p.into_ptr() as *mut ()),
+                drop: fn_drop::<P>(),
+            }
+        };
+        // It is sync since there's no way to interact with a reference to returned type
+        unsafe { self.associate_reference(UnsafeAssertSync(UnsafeAssertSend(inner))) }
+    }
+}
+
+struct ErasedDrop<D: Fn(*mut ())> {
+    ptr: ptr::NonNull<()>,
+    drop: D,
+}
+
+impl<D: Fn(*mut ())> Drop for ErasedDrop<D> {
+    fn drop(&mut self) {
+        (self.drop)(self.ptr.as_ptr())
+    }
+}
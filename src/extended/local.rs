@@ -0,0 +1,134 @@
+use core::future::Future;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task;
+
+use crate::pointer_like::{PointerDeref, PointerDerefMut, PointerIntoInner, PointerPinUnforgotten};
+use crate::{ErasedFn, ErasedFnMut, ErasedFnOnce, ErasedFuture, Extender};
+
+use super::AssociateReference;
+
+/// Extension of closures and futures which aren't [`Send`], like those
+/// capturing [`Rc`](alloc::rc::Rc) or [`RefCell`](core::cell::RefCell),
+/// for single-threaded executors and event loops. Returned values are
+/// `'static`, but not [`Send`] either.
+///
+/// ```
+/// use std::cell::RefCell;
+///
+/// let log = RefCell::new(Vec::new());
+/// scope_lock::lock_scope(|e| {
+///     let f = e.fn_local(Box::new(|s| log.borrow_mut().push(s)));
+///     let run_later: Vec<Box<dyn Fn(&'static str)>> = vec![Box::new(move |s| f(s))];
+///     run_later.iter().for_each(|f| f("hello"));
+/// });
+/// assert_eq!(log.into_inner(), ["hello"]);
+/// ```
+impl<'scope, 'env> Extender<'scope, 'env> {
+    pub fn fn_once_local<P, I, O>(&'scope self, f: P) -> LocalFnOnce<I, O>
+    where
+        P: PointerIntoInner + 'scope,
+        P::Pointee: FnOnce(I) -> O,
+    {
+        LocalFnOnce {
+            inner: unsafe { self.associate_reference(ErasedFnOnce::new(f)) },
+        }
+    }
+
+    pub fn fn_mut_local<P, I, O>(&'scope self, f: P) -> LocalFnMut<I, O>
+    where
+        P: PointerDerefMut + 'scope,
+        P::Pointee: FnMut(I) -> O,
+    {
+        LocalFnMut {
+            inner: unsafe { self.associate_reference(ErasedFnMut::new(f)) },
+        }
+    }
+
+    pub fn fn_local<P, I, O>(&'scope self, f: P) -> LocalFn<I, O>
+    where
+        P: PointerDeref + 'scope,
+        P::Pointee: Fn(I) -> O,
+    {
+        LocalFn {
+            inner: unsafe { self.associate_reference(ErasedFn::new(f)) },
+        }
+    }
+
+    pub fn future_local<P, O>(&'scope self, f: P) -> LocalFuture<O>
+    where
+        P: PointerPinUnforgotten + 'scope,
+        P::Pointee: Future<Output = O>,
+    {
+        LocalFuture {
+            inner: unsafe { self.associate_reference(ErasedFuture::new(f)) },
+        }
+    }
+}
+
+/// Local extended [`FnOnce`] closure returned from
+/// [`Extender::fn_once_local`].
+pub struct LocalFnOnce<I, O> {
+    inner: AssociateReference<ErasedFnOnce<'static, I, O>>,
+}
+
+impl<I, O> LocalFnOnce<I, O> {
+    pub fn call_once(self, input: I) -> O {
+        self.inner.inner.call_once(input)
+    }
+}
+
+/// Local extended [`FnMut`] closure returned from
+/// [`Extender::fn_mut_local`]. Call it through [`DerefMut`].
+pub struct LocalFnMut<I, O> {
+    inner: AssociateReference<ErasedFnMut<'static, I, O>>,
+}
+
+impl<I, O> Deref for LocalFnMut<I, O> {
+    type Target = dyn FnMut(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.inner.inner
+    }
+}
+
+impl<I, O> DerefMut for LocalFnMut<I, O> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.inner.inner
+    }
+}
+
+/// Local extended [`Fn`] closure returned from [`Extender::fn_local`].
+/// Call it through [`Deref`].
+pub struct LocalFn<I, O> {
+    inner: AssociateReference<ErasedFn<'static, I, O>>,
+}
+
+impl<I, O> Clone for LocalFn<I, O> {
+    fn clone(&self) -> Self {
+        LocalFn {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I, O> Deref for LocalFn<I, O> {
+    type Target = dyn Fn(I) -> O;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.inner.inner
+    }
+}
+
+/// Local extended future returned from [`Extender::future_local`].
+pub struct LocalFuture<O> {
+    inner: AssociateReference<ErasedFuture<'static, O>>,
+}
+
+impl<O> Future for LocalFuture<O> {
+    type Output = O;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().inner.inner).poll(cx)
+    }
+}
@@ -1,8 +1,27 @@
 use core::marker::PhantomData;
+use core::ptr;
 
+use crate::pointer_like::PointerLike;
+use crate::pointer_like::erased_static::fn_drop;
+
+pub mod borrow;
+pub mod bounded;
+mod boxed;
 pub mod func;
 pub mod future;
+pub mod group;
+pub mod inline;
+pub mod job_queue;
+mod keep_alive;
+pub mod local;
+pub mod proxy;
+#[cfg(feature = "rayon")]
+mod rayon;
 pub mod sync;
+#[cfg(feature = "tokio")]
+mod tokio;
+pub mod waker;
+pub mod weak;
 
 pub struct Extender<'scope, 'env> {
     rc: &'scope sync::ReferenceCounter,
@@ -19,6 +38,44 @@ impl<'scope, 'env> Extender<'scope, 'env> {
         }
     }
 
+    /// Create a nested scope, like [`lock_scope`](crate::lock_scope),
+    /// which holds a reference to this scope until it ends. Everything
+    /// borrowed for `'scope` could be extended again from the nested
+    /// scope.
+    ///
+    /// Nested scope first waits for its own extended objects, and only
+    /// then releases this scope, so this scope transitively waits for
+    /// nested extended objects as well. That is, when both scopes end,
+    /// nested extended objects are dropped first, then the nested scope
+    /// returns, and only then this scope could end:
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use std::thread;
+    ///
+    /// let log = Mutex::new(Vec::new());
+    /// scope_lock::lock_scope(|e| {
+    ///     let log = e.shared(&log);
+    ///     let f = e.fn_once(Box::new(move |()| {
+    ///         e.nested(|nested| {
+    ///             let log = log.clone();
+    ///             let g = nested.fn_once(Box::new(move |()| log.lock().unwrap().push("nested")));
+    ///             thread::spawn(move || g(()));
+    ///         });
+    ///         log.lock().unwrap().push("after nested");
+    ///     }));
+    ///     thread::spawn(move || f(()));
+    /// });
+    /// assert_eq!(log.into_inner().unwrap(), ["nested", "after nested"]);
+    /// ```
+    pub fn nested<F, T>(&'scope self, scope: F) -> T
+    where
+        F: for<'nested> FnOnce(&'nested Extender<'nested, 'scope>) -> T,
+    {
+        let _parent_reference = unsafe { self.rc.acquire_unbounded() };
+        crate::lock_scope(scope)
+    }
+
     pub(crate) fn guard(&'scope self) -> sync::ReferenceCounterGuard<'scope> {
         self.rc.guard()
     }
@@ -31,14 +88,43 @@ impl<'scope, 'env> Extender<'scope, 'env> {
     }
 }
 
+#[derive(Clone)]
 struct AssociateReference<T> {
     inner: T,
     // drop reference last
     _reference_guard: sync::Reference,
 }
 
+/// Lifetime-erased pointee of a smart-pointer, dropping the original
+/// smart-pointer on drop.
+struct ErasedPtr<T: ?Sized> {
+    ptr: ptr::NonNull<T>,
+    drop: unsafe fn(*mut ()),
+}
+
+impl<T: ?Sized> ErasedPtr<T> {
+    /// # Safety
+    ///
+    /// `ptr` must be returned from [`PointerLike::into_ptr`] of `P`,
+    /// optionally unsized and with its lifetime erased.
+    unsafe fn new<P: PointerLike>(ptr: *mut T) -> Self {
+        ErasedPtr {
+            ptr: unsafe { ptr::NonNull::new_unchecked(ptr) },
+            drop: |ptr| unsafe { fn_drop::<P>()(ptr) },
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for ErasedPtr<T> {
+    fn drop(&mut self) {
+        unsafe { (self.drop)(self.ptr.as_ptr() as *mut ()) }
+    }
+}
+
+#[derive(Clone)]
 struct UnsafeAssertSync<T>(T);
 unsafe impl<T> Sync for UnsafeAssertSync<T> {}
 
+#[derive(Clone)]
 struct UnsafeAssertSend<T>(T);
 unsafe impl<T> Send for UnsafeAssertSend<T> {}
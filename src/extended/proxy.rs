@@ -0,0 +1,164 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::mem::{self, ManuallyDrop};
+use core::panic::AssertUnwindSafe;
+use std::panic;
+use std::thread;
+
+// Model-checked with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`
+#[cfg(loom)]
+use loom::sync::{Condvar, Mutex};
+#[cfg(not(loom))]
+use std::sync::{Condvar, Mutex};
+
+use crate::pointer_like::PointerDeref;
+use crate::{ErasedFn, Extender, Job};
+
+use super::sync::Reference;
+use super::{UnsafeAssertSend, UnsafeAssertSync};
+
+/// Proxy extensions make closures which aren't [`Send`], like those
+/// touching thread-local state, callable from other threads. Each call
+/// is posted to the thread owning the scope and blocks until it is run
+/// there, either by [`Extender::pump`] or while the scope waits for
+/// extended objects at its end. Panics of the closure are propagated to
+/// the caller.
+///
+/// The thread entering the scope owns it, so proxies must be created
+/// on that thread. Scopes of
+/// [`lock_scope_async`](crate::lock_scope_async) have no such thread, and
+/// proxies panic there.
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::thread;
+///
+/// let counter = Cell::new(0);
+/// scope_lock::lock_scope(|e| {
+///     let f = e.proxy(Box::new(|i| {
+///         counter.set(counter.get() + i);
+///         counter.get()
+///     }));
+///     thread::spawn(move || assert_eq!(f.call(2), 2));
+/// });
+/// assert_eq!(counter.get(), 2);
+/// ```
+impl<'scope, 'env> Extender<'scope, 'env> {
+    pub fn proxy<P, I, O>(&'scope self, f: P) -> ProxyFn<I, O>
+    where
+        P: PointerDeref + 'scope,
+        P::Pointee: Fn(I) -> O,
+        I: Send,
+        O: Send,
+    {
+        self.rc.assert_owner();
+        ProxyFn {
+            f: ManuallyDrop::new(UnsafeAssertSync(UnsafeAssertSend(unsafe {
+                ErasedFn::new(f)
+            }))),
+            reference: ManuallyDrop::new(unsafe { self.rc.acquire() }),
+        }
+    }
+
+    /// Run calls of proxies posted so far, returning their count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of the thread owning the scope, or if a
+    /// destructor of a proxied closure panics.
+    pub fn pump(&self) -> usize {
+        self.rc.pump()
+    }
+}
+
+/// Proxy of a closure returned from [`Extender::proxy`].
+pub struct ProxyFn<I, O> {
+    // called and dropped only on the thread owning the scope
+    f: ManuallyDrop<UnsafeAssertSync<UnsafeAssertSend<ErasedFn<'static, I, O>>>>,
+    // drop reference last
+    reference: ManuallyDrop<Reference>,
+}
+
+struct Completion<O> {
+    output: Mutex<Option<thread::Result<O>>>,
+    condvar: Condvar,
+}
+
+impl<I: Send, O: Send> ProxyFn<I, O> {
+    /// Call the closure on the thread owning the scope, blocking until
+    /// it returns. Called from that thread, the closure is run directly.
+    pub fn call(&self, input: I) -> O {
+        let rc = self.reference.counter();
+        if rc.is_owner() {
+            return (self.f.0.0)(input);
+        }
+
+        let completion = Arc::new(Completion {
+            output: Mutex::new(None),
+            condvar: Condvar::new(),
+        });
+        let f = UnsafeAssertSend(&self.f.0.0 as *const ErasedFn<'static, I, O>);
+        let job: Box<dyn FnOnce() + Send + '_> = Box::new({
+            let completion = completion.clone();
+            move || {
+                let f = f;
+                let output = panic::catch_unwind(AssertUnwindSafe(|| unsafe { (*f.0)(input) }));
+                *completion.output.lock().unwrap_or_else(|e| e.into_inner()) = Some(output);
+                completion.condvar.notify_one();
+            }
+        });
+        // Borrows of the job outlive it, since the scope runs it before
+        // releasing the reference, and we wait for that
+        rc.post(unsafe { mem::transmute::<Box<dyn FnOnce() + Send + '_>, Job>(job) });
+
+        let mut output = completion.output.lock().unwrap_or_else(|e| e.into_inner());
+        let output = loop {
+            if let Some(output) = output.take() {
+                break output;
+            }
+            output = completion
+                .condvar
+                .wait(output)
+                .unwrap_or_else(|e| e.into_inner());
+        };
+        output.unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+}
+
+impl<I, O> Clone for ProxyFn<I, O> {
+    fn clone(&self) -> Self {
+        ProxyFn {
+            f: self.f.clone(),
+            reference: self.reference.clone(),
+        }
+    }
+}
+
+impl<I, O> Drop for ProxyFn<I, O> {
+    fn drop(&mut self) {
+        let (f, reference) = unsafe {
+            (
+                ManuallyDrop::take(&mut self.f),
+                ManuallyDrop::take(&mut self.reference),
+            )
+        };
+        if reference.counter().is_owner() {
+            drop(f);
+            return;
+        }
+        let rc = reference.into_raw();
+        let job: Box<dyn FnOnce() + Send + '_> = Box::new({
+            let rc = UnsafeAssertSend(rc);
+            move || {
+                let rc = rc;
+                // released even if the destructor panics
+                let _reference = unsafe { Reference::from_raw(rc.0) };
+                drop(f);
+            }
+        });
+        // Reference is released by the job, after the closure is dropped
+        unsafe {
+            (*rc).post(mem::transmute::<Box<dyn FnOnce() + Send + '_>, Job>(job));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::Extender;
+use crate::pointer_like::{PointerDeref, PointerIntoInner};
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Spawn extended closure onto the rayon thread `pool`, or onto the
+    /// global one if `pool` is `None`.
+    ///
+    /// Keep in mind that the scope blocks its thread until all of the
+    /// spawned closures are done, so avoid waiting for the scope from
+    /// within the same pool.
+    pub fn rayon_spawn<P>(&'scope self, pool: Option<&::rayon::ThreadPool>, f: P)
+    where
+        P: PointerIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(()),
+    {
+        let f = self.fn_once(f);
+        spawn(pool, move || f(()));
+    }
+
+    /// Extend closure once and spawn it for every item onto the rayon
+    /// thread `pool`, or onto the global one if `pool` is `None`.
+    ///
+    /// Same as with [`Self::rayon_spawn`], avoid waiting for the scope
+    /// from within the same pool.
+    pub fn par_for_each_extended<P, T, It>(
+        &'scope self,
+        pool: Option<&::rayon::ThreadPool>,
+        items: It,
+        f: P,
+    ) where
+        P: PointerDeref + Send + 'scope,
+        P::Pointee: Fn(T) + Sync,
+        T: Send + 'static,
+        It: IntoIterator<Item = T>,
+    {
+        let f = self.fn_(f);
+        for item in items {
+            let f = f.clone();
+            spawn(pool, move || f(item));
+        }
+    }
+}
+
+fn spawn<F>(pool: Option<&::rayon::ThreadPool>, f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    match pool {
+        Some(pool) => pool.spawn(f),
+        None => ::rayon::spawn(f),
+    }
+}
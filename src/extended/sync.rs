@@ -1,32 +1,360 @@
-use std::sync::{Condvar, Mutex};
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::panic::AssertUnwindSafe;
+use core::task;
+use std::panic;
+
+// Model-checked with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`
+#[cfg(loom)]
+use loom::sync::{Condvar, Mutex, MutexGuard};
+#[cfg(loom)]
+use loom::thread;
+#[cfg(not(loom))]
+use std::sync::{Condvar, Mutex, MutexGuard};
+#[cfg(not(loom))]
+use std::thread;
+
+use super::job_queue::Job;
+use crate::Waiter;
 
 const ONE_REFERENCE: usize = 2;
 const WAITING_FLAG: usize = 1;
 
 pub struct ReferenceCounter {
-    counter: Mutex<usize>,
+    state: Mutex<State>,
     condvar: Condvar,
+    // maximum number of references, which could be acquired without
+    // blocking
+    limit: usize,
+    // notified once references drop below the limit, or jobs are posted,
+    // while any thread is blocked on it
+    capacity: Condvar,
+}
+
+struct State {
+    counter: usize,
+    // set by an asynchronous waiter of the scope
+    waker: Option<task::Waker>,
+    // taken once the scope starts ending
+    revocable: Option<Vec<Arc<dyn Revoke>>>,
+    // jobs to run on the owner thread
+    mailbox: VecDeque<Job>,
+    // thread which runs jobs posted to the mailbox, recorded on creation
+    owner: Owner,
+    // number of threads blocked on the capacity condvar
+    blocked: usize,
+    // set by asynchronous waiters for the limit
+    capacity_wakers: Vec<task::Waker>,
+    // set by waiters helping with other work meanwhile, which may make
+    // progress after any release
+    wake_on_release: bool,
+}
+
+enum Owner {
+    Thread(thread::ThreadId),
+    // scope may migrate between threads, so proxies are unavailable
+    Migrating,
+}
+
+impl State {
+    fn is_owner(&self) -> bool {
+        matches!(self.owner, Owner::Thread(owner) if owner == thread::current().id())
+    }
+}
+
+/// Waker unparking the thread waiting for the scope.
+struct ThreadWaker(std::thread::Thread);
+
+impl alloc::task::Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Object, which is revoked once the scope starts ending, instead of
+/// being waited for.
+pub(crate) trait Revoke: Send + Sync {
+    fn revoke(&self);
 }
 
 impl ReferenceCounter {
+    /// Counter of a scope, which waits on the current thread.
     pub fn new() -> Self {
+        Self::with_limit(usize::MAX)
+    }
+
+    /// Counter of a scope, which may migrate between threads while
+    /// waiting, so that proxies are unavailable.
+    pub fn migrating() -> Self {
+        let rc = Self::new();
+        rc.lock().owner = Owner::Migrating;
+        rc
+    }
+
+    pub fn with_limit(limit: usize) -> Self {
         Self {
-            counter: Mutex::new(0),
+            state: Mutex::new(State {
+                counter: 0,
+                waker: None,
+                revocable: Some(Vec::new()),
+                mailbox: VecDeque::new(),
+                owner: Owner::Thread(thread::current().id()),
+                blocked: 0,
+                capacity_wakers: Vec::new(),
+                wake_on_release: false,
+            }),
             condvar: Condvar::new(),
+            limit,
+            capacity: Condvar::new(),
         }
     }
 
-    pub unsafe fn acquire(&self) -> Reference {
-        let mut counter = self.counter.lock().unwrap_or_else(|e| e.into_inner());
-        let Some(new_counter) = counter.checked_add(ONE_REFERENCE) else {
-            drop(counter);
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn is_limited(&self, state: &State) -> bool {
+        state.counter / ONE_REFERENCE >= self.limit
+    }
+
+    fn add_reference(&self, mut state: MutexGuard<'_, State>) -> Reference {
+        let Some(new_counter) = state.counter.checked_add(ONE_REFERENCE) else {
+            drop(state);
             panic!("Overflow of extended references count")
         };
-        *counter = new_counter;
+        state.counter = new_counter;
         Reference { rc: self }
     }
 
-    pub fn guard(&self) -> ReferenceCounterGuard {
+    /// Acquire a reference, blocking while the limit is reached. Jobs
+    /// posted meanwhile are run, if called on the owner thread.
+    pub unsafe fn acquire(&self) -> Reference {
+        let mut state = self.lock();
+        if self.is_limited(&state) {
+            let is_owner = state.is_owner();
+            loop {
+                if let Some(job) = is_owner.then(|| state.mailbox.pop_front()).flatten() {
+                    drop(state);
+                    job();
+                    state = self.lock();
+                    continue;
+                }
+                if !self.is_limited(&state) {
+                    break;
+                }
+                state.blocked += 1;
+                state = self.capacity.wait(state).unwrap_or_else(|e| e.into_inner());
+                state.blocked -= 1;
+            }
+        }
+        self.add_reference(state)
+    }
+
+    /// Acquire a reference, unless the limit is reached.
+    pub unsafe fn try_acquire(&self) -> Option<Reference> {
+        let state = self.lock();
+        if self.is_limited(&state) {
+            return None;
+        }
+        Some(self.add_reference(state))
+    }
+
+    /// Acquire a reference, waiting without blocking while the limit is
+    /// reached. Jobs posted meanwhile are run, if polled on the owner
+    /// thread.
+    pub async unsafe fn acquire_async(&self) -> Reference {
+        core::future::poll_fn(|cx| {
+            let mut state = self.lock();
+            let is_owner = state.is_owner();
+            while let Some(job) = is_owner.then(|| state.mailbox.pop_front()).flatten() {
+                drop(state);
+                job();
+                state = self.lock();
+            }
+            if !self.is_limited(&state) {
+                return task::Poll::Ready(self.add_reference(state));
+            }
+            if !state
+                .capacity_wakers
+                .iter()
+                .any(|w| w.will_wake(cx.waker()))
+            {
+                state.capacity_wakers.push(cx.waker().clone());
+            }
+            task::Poll::Pending
+        })
+        .await
+    }
+
+    /// Acquire a reference regardless of the limit, for clones of
+    /// existing references and parent references of nested scopes and
+    /// groups.
+    pub(crate) unsafe fn acquire_unbounded(&self) -> Reference {
+        self.add_reference(self.lock())
+    }
+
+    /// Register object to revoke once the scope starts ending. Returns
+    /// it back if the scope is already ending.
+    pub(crate) fn register(&self, r: Arc<dyn Revoke>) -> Result<(), Arc<dyn Revoke>> {
+        let mut state = self.lock();
+        match &mut state.revocable {
+            Some(revocable) => {
+                revocable.push(r);
+                Ok(())
+            }
+            None => Err(r),
+        }
+    }
+
+    /// Revoke registered objects, and those registered later right away.
+    pub(crate) fn revoke(&self) {
+        let revocable = {
+            let mut state = self.lock();
+            state.revocable.take()
+        };
+        // revoked objects may release references, so do it without a lock
+        for r in revocable.into_iter().flatten() {
+            r.revoke();
+        }
+    }
+
+    /// Revoke objects registered so far, but keep registering new ones
+    /// unless the scope is ending already.
+    pub(crate) fn revoke_registered(&self) {
+        let revocable = {
+            let mut state = self.lock();
+            state.revocable.as_mut().map(core::mem::take)
+        };
+        for r in revocable.into_iter().flatten() {
+            r.revoke();
+        }
+    }
+
+    /// Check if the current thread is the one that created the counter.
+    pub(crate) fn is_owner(&self) -> bool {
+        self.lock().is_owner()
+    }
+
+    /// Check that the current thread is the owner, before handing out
+    /// something which posts jobs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the owner is another thread, or the scope may migrate
+    /// between threads.
+    pub(crate) fn assert_owner(&self) {
+        let state = self.lock();
+        match state.owner {
+            Owner::Thread(owner) if owner == thread::current().id() => {}
+            Owner::Thread(_) => {
+                drop(state);
+                panic!("proxy is created outside of the scope's thread")
+            }
+            Owner::Migrating => {
+                drop(state);
+                panic!("proxies are unavailable in scopes, which may migrate between threads")
+            }
+        }
+    }
+
+    /// Post a job to run on the owner thread, either by [`Self::pump`] or
+    /// while the guard waits. Jobs are run only while the counter is
+    /// alive, so the caller should hold a reference until its job is done.
+    pub(crate) fn post(&self, job: Job) {
+        let (waker, capacity_wakers) = {
+            let mut state = self.lock();
+            state.mailbox.push_back(job);
+            self.condvar.notify_one();
+            // the owner thread may be waiting for the limit as well
+            if state.blocked != 0 {
+                self.capacity.notify_all();
+            }
+            (
+                state.waker.take(),
+                core::mem::take(&mut state.capacity_wakers),
+            )
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        capacity_wakers.into_iter().for_each(task::Waker::wake);
+    }
+
+    fn pop_job(&self) -> Option<Job> {
+        let mut state = self.lock();
+        state.mailbox.pop_front()
+    }
+
+    /// Run posted jobs, returning their count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of the owner thread.
+    pub(crate) fn pump(&self) -> usize {
+        let state = self.lock();
+        // no proxies, no jobs
+        if matches!(state.owner, Owner::Migrating) {
+            return 0;
+        }
+        let is_owner = state.is_owner();
+        drop(state);
+        assert!(
+            is_owner,
+            "mailbox of the scope is pumped outside of the scope's thread"
+        );
+        let mut count = 0;
+        // one by one, so that a panicking job doesn't lose the rest
+        while let Some(job) = self.pop_job() {
+            job();
+            count += 1;
+        }
+        count
+    }
+
+    /// Block until every reference is released, running posted jobs
+    /// meanwhile. Doesn't revoke registered objects.
+    ///
+    /// # Panics
+    ///
+    /// Propagates panics of posted jobs, but only after every reference
+    /// is released.
+    pub(crate) fn block(&self) {
+        let mut panic = None;
+        // NOTE: establishes acquire ordering
+        let mut state = self.lock();
+        loop {
+            // jobs posted from another thread are never run here otherwise
+            let is_owner = state.is_owner();
+            if let Some(job) = is_owner.then(|| state.mailbox.pop_front()).flatten() {
+                drop(state);
+                // keep waiting regardless, references are still alive
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                    panic.get_or_insert(payload);
+                }
+                state = self.lock();
+                continue;
+            }
+            if state.counter & !WAITING_FLAG == 0 {
+                state.counter = 0;
+                break;
+            }
+            state.counter |= WAITING_FLAG;
+            state = self.condvar.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        drop(state);
+        if let Some(payload) = panic {
+            if !std::thread::panicking() {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+
+    pub fn guard(&self) -> ReferenceCounterGuard<'_> {
         ReferenceCounterGuard { rc: self }
     }
 }
@@ -35,18 +363,65 @@ pub struct Reference {
     rc: *const ReferenceCounter,
 }
 
+impl Reference {
+    pub(crate) fn counter(&self) -> &ReferenceCounter {
+        // Reference counter outlives any reference to it
+        unsafe { &*self.rc }
+    }
+
+    /// Forget reference without releasing it, so it could be restored
+    /// later with [`Self::from_raw`].
+    pub(crate) fn into_raw(self) -> *const ReferenceCounter {
+        let this = core::mem::ManuallyDrop::new(self);
+        this.rc
+    }
+
+    /// # Safety
+    ///
+    /// `rc` must have been returned from [`Self::into_raw`], and only
+    /// once per such call.
+    pub(crate) unsafe fn from_raw(rc: *const ReferenceCounter) -> Self {
+        Reference { rc }
+    }
+}
+
 impl Drop for Reference {
     fn drop(&mut self) {
         // NOTE: establishes release ordering
-        unsafe {
-            let mut counter = (*self.rc).counter.lock().unwrap_or_else(|e| e.into_inner());
-            let new_counter = *counter - ONE_REFERENCE;
-            *counter = new_counter;
+        let (waker, capacity_wakers) = unsafe {
+            let rc = &*self.rc;
+            let mut state = rc.lock();
+            let new_counter = state.counter - ONE_REFERENCE;
+            state.counter = new_counter;
+            let mut capacity_wakers = Vec::new();
+            if !rc.is_limited(&state) {
+                if state.blocked != 0 {
+                    rc.capacity.notify_one();
+                }
+                capacity_wakers = core::mem::take(&mut state.capacity_wakers);
+            }
             // notify only if scope already waits
+            let mut waker = None;
             if new_counter == WAITING_FLAG {
-                (*self.rc).condvar.notify_one();
+                rc.condvar.notify_one();
+                waker = state.waker.take();
+            } else if state.wake_on_release {
+                waker = state.waker.take();
             }
+            (waker, capacity_wakers)
+        };
+        // reference counter might be already gone at this point
+        if let Some(waker) = waker {
+            waker.wake();
         }
+        capacity_wakers.into_iter().for_each(task::Waker::wake);
+    }
+}
+
+impl Clone for Reference {
+    fn clone(&self) -> Self {
+        // Reference counter outlives any reference to it
+        unsafe { (*self.rc).acquire_unbounded() }
     }
 }
 
@@ -57,24 +432,94 @@ pub struct ReferenceCounterGuard<'a> {
     rc: &'a ReferenceCounter,
 }
 
-impl<'a> Drop for ReferenceCounterGuard<'a> {
-    fn drop(&mut self) {
-        // NOTE: establishes acquire ordering
-        let mut counter = self.rc.counter.lock().unwrap_or_else(|e| e.into_inner());
-        if *counter == 0 {
+impl ReferenceCounterGuard<'_> {
+    /// Revoke registered objects. Should precede any waiting.
+    fn revoke(&self) {
+        self.rc.revoke();
+    }
+
+    /// Run posted jobs, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are posted jobs, but the guard is used outside of
+    /// the owner thread, since they would never run otherwise.
+    fn pump(&self) {
+        if self.rc.is_owner() {
+            self.rc.pump();
             return;
         }
-        *counter |= WAITING_FLAG;
-        loop {
-            counter = self
-                .rc
-                .condvar
-                .wait(counter)
-                .unwrap_or_else(|e| e.into_inner());
+        let state = self.rc.lock();
+        let has_jobs = !state.mailbox.is_empty();
+        drop(state);
+        assert!(
+            !has_jobs,
+            "scope with posted jobs is waited for outside of the scope's thread"
+        );
+    }
 
-            if *counter == WAITING_FLAG {
-                return;
+    /// Wait for every reference to be released, helping with `waiter`
+    /// meanwhile. Whenever it has nothing to do, it is parked until a
+    /// reference is released or a job is posted, unless it's woken up by
+    /// its own means earlier. Posted jobs are run first.
+    pub fn wait_helping<W: Waiter + ?Sized>(&self, waiter: &mut W) {
+        self.revoke();
+        let waker = task::Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        loop {
+            self.pump();
+            {
+                // NOTE: establishes acquire ordering
+                let mut state = self.rc.lock();
+                if state.counter & !WAITING_FLAG == 0 {
+                    state.counter = 0;
+                    state.waker = None;
+                    state.wake_on_release = false;
+                    return;
+                }
+                // registered before helping, so that releases in between
+                // aren't missed
+                state.counter |= WAITING_FLAG;
+                state.wake_on_release = true;
+                match &mut state.waker {
+                    Some(w) => w.clone_from(&waker),
+                    w @ None => *w = Some(waker.clone()),
+                }
+            }
+            if !waiter.try_help() {
+                waiter.park(&waker);
             }
         }
     }
+
+    /// Wait for every reference to be released without blocking. If
+    /// returned future is dropped before completion, waiting continues
+    /// on drop of the guard. Posted jobs are run on each poll, so it
+    /// panics if polled outside of the owner thread while any are posted.
+    pub async fn wait(&self) {
+        self.revoke();
+        core::future::poll_fn(|cx| {
+            self.pump();
+            // NOTE: establishes acquire ordering
+            let mut state = self.rc.lock();
+            if state.counter & !WAITING_FLAG == 0 {
+                state.counter = 0;
+                state.waker = None;
+                return task::Poll::Ready(());
+            }
+            state.counter |= WAITING_FLAG;
+            match &mut state.waker {
+                Some(waker) => waker.clone_from(cx.waker()),
+                waker @ None => *waker = Some(cx.waker().clone()),
+            }
+            task::Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<'a> Drop for ReferenceCounterGuard<'a> {
+    fn drop(&mut self) {
+        self.revoke();
+        self.rc.block();
+    }
 }
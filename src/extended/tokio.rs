@@ -0,0 +1,33 @@
+use core::future::Future;
+
+use ::tokio::task::JoinHandle;
+
+use crate::Extender;
+use crate::pointer_like::{PointerIntoInner, PointerPinUnforgotten};
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Spawn extended future onto the current tokio runtime.
+    ///
+    /// Use [`lock_scope_async`](crate::lock_scope_async) to avoid blocking
+    /// runtime's worker thread while waiting for spawned tasks.
+    pub fn tokio_spawn<P, O>(&'scope self, f: P) -> JoinHandle<O>
+    where
+        P: PointerPinUnforgotten + Send + 'scope,
+        P::Pointee: Future<Output = O>,
+        O: Send + 'static,
+    {
+        ::tokio::spawn(self.future(f))
+    }
+
+    /// Run extended closure on the blocking thread pool of the current
+    /// tokio runtime.
+    pub fn tokio_spawn_blocking<P, O>(&'scope self, f: P) -> JoinHandle<O>
+    where
+        P: PointerIntoInner + Send + 'scope,
+        P::Pointee: FnOnce(()) -> O,
+        O: Send + 'static,
+    {
+        let f = self.fn_once(f);
+        ::tokio::task::spawn_blocking(move || f(()))
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::sync::Arc;
+use core::marker::PhantomData;
+use core::ptr;
+use core::task;
+
+use crate::Extender;
+use crate::pointer_like::PointerDeref;
+
+use super::sync::{Reference, ReferenceCounter};
+
+/// Wake logic for [`Extender::waker`]. Unlike [`std::task::Wake`] it
+/// is implemented over a plain reference, so it can be borrowed from the
+/// scheduler state.
+pub trait WakeRef {
+    fn wake_by_ref(&self);
+}
+
+impl<'scope, 'env> Extender<'scope, 'env> {
+    /// Create a [`Waker`](task::Waker) from a borrowed wake logic. Every
+    /// clone of the returned waker holds its own reference to the scope,
+    /// so the scope waits until all of them are dropped.
+    pub fn waker<P>(&'scope self, p: P) -> task::Waker
+    where
+        P: PointerDeref + Send + 'scope,
+        P::Pointee: WakeRef + Sync,
+    {
+        let shared = Arc::new(Shared::<P> {
+            ptr: unsafe { ptr::NonNull::new_unchecked(p.into_ptr()) },
+            rc: self.rc,
+        });
+        let reference = unsafe { self.rc.acquire() };
+        // Each raw waker owns a strong count of `shared` and a reference
+        reference.into_raw();
+        let raw = task::RawWaker::new(Arc::into_raw(shared) as *const (), Shared::<P>::vtable());
+        unsafe { task::Waker::from_raw(raw) }
+    }
+}
+
+struct Shared<P: PointerDeref> {
+    ptr: ptr::NonNull<P::Pointee>,
+    rc: *const ReferenceCounter,
+}
+
+impl<P> Shared<P>
+where
+    P: PointerDeref,
+    P::Pointee: WakeRef,
+{
+    const VTABLE: task::RawWakerVTable =
+        task::RawWakerVTable::new(Self::clone, Self::wake, Self::wake_by_ref, Self::drop);
+
+    // single promoted constant, so that `Waker::will_wake` recognizes clones
+    fn vtable() -> &'static task::RawWakerVTable {
+        &Self::VTABLE
+    }
+
+    unsafe fn clone(data: *const ()) -> task::RawWaker {
+        let this = data as *const Self;
+        unsafe {
+            Arc::increment_strong_count(this);
+            // Reference counter outlives any reference to it
+            (*(*this).rc).acquire_unbounded().into_raw();
+        }
+        task::RawWaker::new(data, Self::vtable())
+    }
+
+    unsafe fn wake(data: *const ()) {
+        // released even if the wake logic panics
+        let _guard = DropOnExit::<P>(data, PhantomData);
+        unsafe { Self::wake_by_ref(data) }
+    }
+
+    unsafe fn wake_by_ref(data: *const ()) {
+        let this = data as *const Self;
+        unsafe { (*this).ptr.as_ref().wake_by_ref() }
+    }
+
+    unsafe fn drop(data: *const ()) {
+        let this = data as *const Self;
+        unsafe {
+            // drop the pointer before releasing the reference, even if
+            // its destructor panics
+            let _reference = Reference::from_raw((*this).rc);
+            Arc::decrement_strong_count(this);
+        }
+    }
+}
+
+/// Drops a raw waker once it goes out of scope.
+struct DropOnExit<P>(*const (), PhantomData<P>)
+where
+    P: PointerDeref,
+    P::Pointee: WakeRef;
+
+impl<P> Drop for DropOnExit<P>
+where
+    P: PointerDeref,
+    P::Pointee: WakeRef,
+{
+    fn drop(&mut self) {
+        unsafe { Shared::<P>::drop(self.0) }
+    }
+}
+
+impl<P: PointerDeref> Drop for Shared<P> {
+    fn drop(&mut self) {
+        drop(unsafe { P::from_ptr(self.ptr.as_ptr()) })
+    }
+}
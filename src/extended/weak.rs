@@ -0,0 +1,146 @@
+use alloc::sync::Arc;
+use core::mem;
+
+// Model-checked with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`
+#[cfg(loom)]
+use loom::sync::{Mutex, RwLock};
+#[cfg(not(loom))]
+use std::sync::{Mutex, RwLock};
+
+use crate::pointer_like::{PointerDeref, PointerDerefMut};
+use crate::{ErasedFn, ErasedFnMut, Extender};
+
+use super::sync::Revoke;
+use super::{UnsafeAssertSend, UnsafeAssertSync};
+
+/// Weak extensions don't keep the scope from ending. Instead, once the
+/// scope starts ending, it waits only for calls currently in flight and
+/// drops the closure itself, so that later calls return `None`.
+///
+/// ```
+/// use std::sync::Mutex;
+///
+/// let registry: Mutex<Vec<scope_lock::WeakFn<(), usize>>> = Mutex::new(Vec::new());
+/// let data = vec![1, 2, 3];
+/// scope_lock::lock_scope(|e| {
+///     let f = e.fn_weak(Box::new(|()| data.len()));
+///     assert_eq!(f.call(()), Some(3));
+///     registry.lock().unwrap().push(f);
+/// });
+/// assert_eq!(registry.lock().unwrap()[0].call(()), None);
+/// ```
+impl<'scope, 'env> Extender<'scope, 'env> {
+    pub fn fn_weak<P, I, O>(&'scope self, f: P) -> WeakFn<I, O>
+    where
+        P: PointerDeref + Send + 'scope,
+        P::Pointee: Fn(I) -> O + Sync,
+        I: Send,
+        O: Send,
+    {
+        let f = unsafe { ErasedFn::new(f) };
+        let state = Arc::new(RwLock::new(Some(UnsafeAssertSync(UnsafeAssertSend(f)))));
+        self.register(state.clone());
+        WeakFn { state }
+    }
+
+    /// Same as [`Self::fn_weak`], but calls are serialized by a lock.
+    /// Calling the closure from itself deadlocks.
+    pub fn fn_mut_weak<P, I, O>(&'scope self, f: P) -> WeakFnMut<I, O>
+    where
+        P: PointerDerefMut + Send + 'scope,
+        P::Pointee: FnMut(I) -> O,
+        I: Send,
+        O: Send,
+    {
+        let f = unsafe { ErasedFnMut::new(f) };
+        let state = Arc::new(Mutex::new(Some(UnsafeAssertSend(f))));
+        self.register(state.clone());
+        WeakFnMut { state }
+    }
+
+    fn register(&'scope self, state: Arc<dyn Revoke + '_>) {
+        // Revoked before the end of the scope
+        let state = unsafe { mem::transmute::<Arc<dyn Revoke + '_>, Arc<dyn Revoke>>(state) };
+        // Scope is already ending, but still alive, since it's borrowed
+        if let Err(state) = self.rc.register(state) {
+            state.revoke();
+        }
+    }
+}
+
+type SharedFn<I, O> = RwLock<Option<UnsafeAssertSync<UnsafeAssertSend<ErasedFn<'static, I, O>>>>>;
+
+/// Weak extended [`Fn`] closure returned from [`Extender::fn_weak`].
+pub struct WeakFn<I, O> {
+    state: Arc<SharedFn<I, O>>,
+}
+
+impl<I, O> WeakFn<I, O> {
+    /// Call the closure, unless the scope has already ended.
+    pub fn call(&self, input: I) -> Option<O> {
+        let f = self.state.read().unwrap_or_else(|e| e.into_inner());
+        f.as_ref().map(|f| (f.0.0)(input))
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_none()
+    }
+}
+
+impl<I, O> Clone for WeakFn<I, O> {
+    fn clone(&self) -> Self {
+        WeakFn {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<I, O> Revoke for SharedFn<I, O> {
+    fn revoke(&self) {
+        // waits for calls in flight
+        let f = self.write().unwrap_or_else(|e| e.into_inner()).take();
+        drop(f);
+    }
+}
+
+type SharedFnMut<I, O> = Mutex<Option<UnsafeAssertSend<ErasedFnMut<'static, I, O>>>>;
+
+/// Weak extended [`FnMut`] closure returned from
+/// [`Extender::fn_mut_weak`].
+pub struct WeakFnMut<I, O> {
+    state: Arc<SharedFnMut<I, O>>,
+}
+
+impl<I, O> WeakFnMut<I, O> {
+    /// Call the closure, unless the scope has already ended.
+    pub fn call(&self, input: I) -> Option<O> {
+        let mut f = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        f.as_mut().map(|f| (f.0)(input))
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_none()
+    }
+}
+
+impl<I, O> Clone for WeakFnMut<I, O> {
+    fn clone(&self) -> Self {
+        WeakFnMut {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<I, O> Revoke for SharedFnMut<I, O> {
+    fn revoke(&self) {
+        // waits for the call in flight
+        let f = self.lock().unwrap_or_else(|e| e.into_inner()).take();
+        drop(f);
+    }
+}
@@ -30,25 +30,173 @@
 // TODO: gate under a feature
 extern crate alloc;
 
+use core::task;
+
 mod extended;
 pub mod pointer_like;
 mod ref_once;
 
 pub use extended::Extender;
-pub use extended::func::{extend_fn_mut_unchecked, extend_fn_once_unchecked, extend_fn_unchecked};
-pub use extended::future::extend_future_unchecked;
-pub use extended::future::legacy::ExtendedFuture;
-pub use ref_once::RefOnce;
+pub use extended::borrow::{Extended, ExtendedMut};
+pub use extended::bounded::{LimitReached, Reservation};
+pub use extended::func::{
+    ErasedFn, ErasedFnMut, ErasedFnOnce, ExtendedFn, ExtendedFnMut, ExtendedFnOnce,
+    ExtendedFnUnsync, extend_fn_mut_unchecked, extend_fn_once_shared_or_ref_unchecked,
+    extend_fn_once_shared_unchecked, extend_fn_once_unchecked, extend_fn_unchecked,
+};
+pub use extended::future::{ErasedFuture, ExtendedFuture, extend_future_unchecked};
+pub use extended::group::Group;
+pub use extended::inline::{InlineFn, InlineFnMut, InlineFnOnce};
+pub use extended::job_queue::{ExtendedSender, Job, JobQueue};
+pub use extended::local::{LocalFn, LocalFnMut, LocalFnOnce, LocalFuture};
+pub use extended::proxy::ProxyFn;
+pub use extended::waker::WakeRef;
+pub use extended::weak::{WeakFn, WeakFnMut};
+pub use ref_once::{PinnedRefOnce, PooledRefOnce, RefOnce, SlotPool};
+
+/// Set up a scope over the reference counter `$rc`, binding its extender
+/// and the guard waiting for extended objects once dropped.
+macro_rules! enter_scope {
+    ($rc:expr, $extender:ident, $guard:ident) => {
+        let rw_lock = $rc;
+        let $extender = Extender::new(&rw_lock);
+        let $guard = $extender.guard();
+    };
+}
 
+/// Run `scope` and wait until every object extended through its
+/// [`Extender`] is dropped.
 pub fn lock_scope<'env, F, T>(scope: F) -> T
 where
     F: for<'scope> FnOnce(&'scope Extender<'scope, 'env>) -> T,
 {
-    let rw_lock = extended::sync::ReferenceCounter::new();
-    let extender = Extender::new(&rw_lock);
-    let _guard = extender.guard();
+    enter_scope!(extended::sync::ReferenceCounter::new(), extender, _guard);
+    scope(&extender)
+}
+
+/// Same as [`lock_scope`], but at most `max` extended objects could be
+/// alive at once, so that producers of them don't outrun consumers.
+/// Extension blocks while the limit is reached, see
+/// [`Extender::reserve`] for alternatives.
+///
+/// Clones of extended objects aren't blocked, but count toward the limit.
+/// Nested scopes and groups have their own unbounded counters. Extension
+/// blocks forever if only the current thread could release extended
+/// objects, like when they are collected into a vector.
+///
+/// # Panics
+///
+/// Panics if `max` is zero.
+pub fn lock_scope_bounded<'env, F, T>(max: usize, scope: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Extender<'scope, 'env>) -> T,
+{
+    assert!(max != 0, "lock_scope_bounded: limit must be positive");
+    enter_scope!(extended::sync::ReferenceCounter::with_limit(max), extender, _guard);
     scope(&extender)
 }
 
-// TODO: zero case test
-// TODO: tests from rust std::thread::scope
+/// Same as [`lock_scope`], but instead of blocking the current thread
+/// waits for extended objects by driving a local `executor`, like
+/// [`LocalPool::try_run_one`](https://docs.rs/futures/0.3/futures/executor/struct.LocalPool.html#method.try_run_one).
+/// `executor` is called repeatedly until every extended object is
+/// dropped, and should return `false` once it cannot make any progress,
+/// same as [`Waiter::try_help`]. Then it's parked with [`Waiter::park`]
+/// until any of its tasks is woken, or an extended object is released,
+/// and tried again.
+///
+/// Since the executor could be woken by anything, like a timer or
+/// another thread, the scope never assumes it's stuck. If nothing wakes
+/// it, the scope blocks forever, same as [`lock_scope`] with extended
+/// objects kept alive. `executor` is dropped after the scope is done
+/// waiting.
+pub fn lock_scope_local<'env, E, F, T>(executor: E, scope: F) -> T
+where
+    E: Waiter,
+    F: for<'scope> FnOnce(&'scope Extender<'scope, 'env>) -> T,
+{
+    enter_scope!(extended::sync::ReferenceCounter::new(), extender, guard);
+    let mut executor = executor;
+    let output = scope(&extender);
+    guard.wait_helping(&mut executor);
+    output
+}
+
+/// Work done by the scope's thread while it waits for extended objects,
+/// like running pending jobs of a work-stealing thread pool.
+pub trait Waiter {
+    /// Do a piece of work, returning `false` if there's nothing to do.
+    fn try_help(&mut self) -> bool;
+
+    /// Block until [`Self::try_help`] might have something to do again,
+    /// or `waker` is woken by the scope once an extended object is
+    /// released. Spurious wakeups are fine, since the scope calls
+    /// [`Self::try_help`] and parks again.
+    ///
+    /// By default parks the current thread, which `waker` unparks. That
+    /// suits executors which unpark their thread to wake tasks, like
+    /// [`LocalPool`](https://docs.rs/futures/0.3/futures/executor/struct.LocalPool.html)
+    /// does.
+    fn park(&mut self, waker: &task::Waker) {
+        let _ = waker;
+        std::thread::park();
+    }
+}
+
+impl<F: FnMut() -> bool> Waiter for F {
+    fn try_help(&mut self) -> bool {
+        self()
+    }
+}
+
+/// Same as [`lock_scope`], but helps with `waiter` while extended
+/// objects are alive instead of sitting idle, like [`rayon::join`] does
+/// when waiting for a stolen job. Whenever `waiter` has nothing to do,
+/// it's parked with [`Waiter::park`] until an extended object is
+/// released, and then helps again, since other extended objects could
+/// push more work meanwhile.
+///
+/// For example, waiting within a rayon thread pool for jobs spawned onto
+/// the same pool may use `|| rayon::yield_now() == Some(Yield::Executed)`
+/// as a waiter to avoid exhausting the pool.
+///
+/// [`rayon::join`]: https://docs.rs/rayon/1/rayon/fn.join.html
+pub fn lock_scope_with_waiter<'env, W, F, T>(waiter: W, scope: F) -> T
+where
+    W: Waiter,
+    F: for<'scope> FnOnce(&'scope Extender<'scope, 'env>) -> T,
+{
+    enter_scope!(extended::sync::ReferenceCounter::new(), extender, guard);
+    let mut waiter = waiter;
+    let output = scope(&extender);
+    guard.wait_helping(&mut waiter);
+    output
+}
+
+/// Same as [`lock_scope`], but waits for extended objects without
+/// blocking the current thread, which is crucial for extended futures
+/// spawned onto the same asynchronous runtime.
+///
+/// # Safety
+///
+/// Returned future must be polled to completion or dropped, but never
+/// forgotten. Scope falls back to blocking the current thread if the
+/// future is dropped before completion.
+///
+/// # Panics
+///
+/// Proxies panic on creation, since the future may migrate between
+/// threads, so there's no thread to run their calls on.
+pub async unsafe fn lock_scope_async<'env, F, T>(scope: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Extender<'scope, 'env>) -> T,
+{
+    enter_scope!(
+        extended::sync::ReferenceCounter::migrating(),
+        extender,
+        guard
+    );
+    let output = scope(&extender);
+    guard.wait().await;
+    output
+}
@@ -1,7 +1,7 @@
 use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
 
-use crate::RefOnce;
+use crate::{PinnedRefOnce, PooledRefOnce, RefOnce};
 
 pub mod erased_static;
 
@@ -81,6 +81,30 @@ pub unsafe trait PointerIntoInner: PointerDerefMut + DerefMut {
     fn into_inner(self) -> Self::Pointee;
 }
 
+/// Trait that allows to move pointed-at object out of a shared
+/// smart-pointer, once it's the only owner of the object.
+///
+/// # Safety
+///
+/// Implementer must guarantee safety of moving out of the original
+/// smart-pointer via [`Self::into_inner`] and [`Self::try_into_inner`]
+/// methods, and that the smart-pointer returned on failure is the same
+/// one.
+pub unsafe trait PointerTryIntoInner: PointerDeref + Sized {
+    /// Move the object out if this is the last owner of it, otherwise
+    /// drop the smart-pointer, like
+    /// [`Arc::into_inner`](alloc::sync::Arc::into_inner) does. Out of
+    /// owners dropped concurrently, exactly one gets the object.
+    fn into_inner(self) -> Option<Self::Pointee>;
+
+    /// Move the object out if this is the only owner of it, otherwise
+    /// give the smart-pointer back, like
+    /// [`Arc::try_unwrap`](alloc::sync::Arc::try_unwrap) does. Unlike
+    /// [`Self::into_inner`], it may fail for every one of owners dropped
+    /// concurrently, but the object is still reachable on failure.
+    fn try_into_inner(self) -> Result<Self::Pointee, Self>;
+}
+
 /// Trait that allows to create pinned mutable references to the
 /// pointed-at object, when assuming that the original smart-pointer
 /// won't leak.
@@ -164,6 +188,15 @@ unsafe impl<T> PointerLike for alloc::rc::Rc<T> {
     }
 }
 unsafe impl<T> PointerDeref for alloc::rc::Rc<T> {}
+unsafe impl<T> PointerTryIntoInner for alloc::rc::Rc<T> {
+    fn into_inner(self) -> Option<Self::Pointee> {
+        alloc::rc::Rc::into_inner(self)
+    }
+
+    fn try_into_inner(self) -> Result<Self::Pointee, Self> {
+        alloc::rc::Rc::try_unwrap(self)
+    }
+}
 
 unsafe impl<T> PointerLike for alloc::sync::Arc<T> {
     type Pointee = T;
@@ -177,6 +210,15 @@ unsafe impl<T> PointerLike for alloc::sync::Arc<T> {
     }
 }
 unsafe impl<T> PointerDeref for alloc::sync::Arc<T> {}
+unsafe impl<T> PointerTryIntoInner for alloc::sync::Arc<T> {
+    fn into_inner(self) -> Option<Self::Pointee> {
+        alloc::sync::Arc::into_inner(self)
+    }
+
+    fn try_into_inner(self) -> Result<Self::Pointee, Self> {
+        alloc::sync::Arc::try_unwrap(self)
+    }
+}
 
 unsafe impl<Ptr: PointerDeref> PointerLike for Pin<Ptr> {
     type Pointee = Ptr::Pointee;
@@ -230,3 +272,46 @@ unsafe impl<T> PointerIntoInner for RefOnce<'_, T> {
 ///
 /// [pin's drop guarantee]: https://doc.rust-lang.org/nightly/std/pin/index.html#subtle-details-and-the-drop-guarantee
 unsafe impl<T> PointerPinUnforgotten for RefOnce<'_, T> {}
+
+unsafe impl<'a, T> PointerLike for PinnedRefOnce<'a, T> {
+    type Pointee = T;
+
+    fn into_ptr(self) -> *mut Self::Pointee {
+        PinnedRefOnce::into_raw(self)
+    }
+
+    unsafe fn from_ptr(ptr: *mut Self::Pointee) -> Self {
+        unsafe { PinnedRefOnce::from_raw(ptr) }
+    }
+}
+unsafe impl<T> PointerDeref for PinnedRefOnce<'_, T> {}
+unsafe impl<T: Unpin> PointerDerefMut for PinnedRefOnce<'_, T> {}
+unsafe impl<T: Unpin> PointerIntoInner for PinnedRefOnce<'_, T> {
+    fn into_inner(self) -> Self::Pointee {
+        PinnedRefOnce::into_inner(self)
+    }
+}
+/// Guaranteed by the [`PinnedRefOnce::new_unchecked`] caller
+unsafe impl<T> PointerPinUnforgotten for PinnedRefOnce<'_, T> {}
+
+unsafe impl<'a, T> PointerLike for PooledRefOnce<'a, T> {
+    type Pointee = T;
+
+    fn into_ptr(self) -> *mut Self::Pointee {
+        PooledRefOnce::into_raw(self)
+    }
+
+    unsafe fn from_ptr(ptr: *mut Self::Pointee) -> Self {
+        unsafe { PooledRefOnce::from_raw(ptr) }
+    }
+}
+unsafe impl<T> PointerDeref for PooledRefOnce<'_, T> {}
+unsafe impl<T> PointerDerefMut for PooledRefOnce<'_, T> {}
+unsafe impl<T> PointerIntoInner for PooledRefOnce<'_, T> {
+    fn into_inner(self) -> Self::Pointee {
+        PooledRefOnce::into_inner(self)
+    }
+}
+/// Same reasoning as for [`RefOnce`] applies, since the slot is only
+/// released after the object is dropped.
+unsafe impl<T> PointerPinUnforgotten for PooledRefOnce<'_, T> {}
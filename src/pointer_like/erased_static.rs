@@ -11,7 +11,10 @@ use core::{
     task,
 };
 
-use super::{PointerDeref, PointerDerefMut, PointerIntoInner, PointerLike, PointerPinUnforgotten};
+use super::{
+    PointerDeref, PointerDerefMut, PointerIntoInner, PointerLike, PointerPinUnforgotten,
+    PointerTryIntoInner,
+};
 
 /// Get `'static` function of drop on a `P` pointer type.
 ///
@@ -24,6 +27,12 @@ pub const unsafe fn fn_drop<P: PointerLike>(
     |erased_ptr| drop(unsafe { P::from_ptr(erased_ptr as *mut P::Pointee) })
 }
 
+/// Get `'static` function calling [`Fn`] behind a `P` pointer type.
+///
+/// # Safety
+///
+/// Only valid pointers of the same original smart-pointer type `P` must be passed to the returned
+/// closure otherwise causing undefined behaviour.
 pub const unsafe fn fn_call<P, I, O>(
 ) -> impl Fn(*const (), I) -> O + Copy + Send + Sync + UnwindSafe + RefUnwindSafe + Unpin + 'static
 where
@@ -33,6 +42,12 @@ where
     |erased_ptr, input| unsafe { (*(erased_ptr as *const P::Pointee))(input) }
 }
 
+/// Get `'static` function calling [`FnMut`] behind a `P` pointer type.
+///
+/// # Safety
+///
+/// Only valid pointers of the same original smart-pointer type `P` must be passed to the returned
+/// closure otherwise causing undefined behaviour.
 pub const unsafe fn fn_call_mut<P, I, O>(
 ) -> impl Fn(*mut (), I) -> O + Copy + Send + Sync + UnwindSafe + RefUnwindSafe + Unpin + 'static
 where
@@ -42,6 +57,12 @@ where
     |erased_ptr, input| unsafe { (*(erased_ptr as *mut P::Pointee))(input) }
 }
 
+/// Get `'static` function moving [`FnOnce`] out of a `P` pointer type and calling it.
+///
+/// # Safety
+///
+/// Only valid pointers of the same original smart-pointer type `P` must be passed to the returned
+/// closure otherwise causing undefined behaviour.
 pub const unsafe fn fn_call_once<P, I, O>(
 ) -> impl Fn(*mut (), I) -> O + Copy + Send + Sync + UnwindSafe + RefUnwindSafe + Unpin + 'static
 where
@@ -51,6 +72,51 @@ where
     |erased_ptr, input| (unsafe { P::from_ptr(erased_ptr as *mut P::Pointee) }).into_inner()(input)
 }
 
+/// Get `'static` function moving [`FnOnce`] out of a shared `P` pointer type and calling it, if
+/// the pointer is the last owner of it.
+///
+/// # Safety
+///
+/// Only valid pointers of the same original smart-pointer type `P` must be passed to the returned
+/// closure otherwise causing undefined behaviour.
+pub const unsafe fn fn_call_once_shared<P, I, O>(
+) -> impl Fn(*mut (), I) -> Option<O> + Copy + Send + Sync + UnwindSafe + RefUnwindSafe + Unpin + 'static
+where
+    P: PointerTryIntoInner,
+    P::Pointee: FnOnce(I) -> O,
+{
+    |erased_ptr, input| {
+        (unsafe { P::from_ptr(erased_ptr as *mut P::Pointee) })
+            .into_inner()
+            .map(|f| f(input))
+    }
+}
+
+/// Get `'static` function moving [`Fn`] out of a shared `P` pointer type and calling it, or
+/// calling it by reference if the pointer isn't the only owner of it.
+///
+/// # Safety
+///
+/// Only valid pointers of the same original smart-pointer type `P` must be passed to the returned
+/// closure otherwise causing undefined behaviour.
+pub const unsafe fn fn_call_once_shared_or_ref<P, I, O>(
+) -> impl Fn(*mut (), I) -> O + Copy + Send + Sync + UnwindSafe + RefUnwindSafe + Unpin + 'static
+where
+    P: PointerTryIntoInner,
+    P::Pointee: Fn(I) -> O,
+{
+    |erased_ptr, input| match (unsafe { P::from_ptr(erased_ptr as *mut P::Pointee) }).try_into_inner() {
+        Ok(f) => f(input),
+        Err(p) => (*p)(input),
+    }
+}
+
+/// Get `'static` function polling pinned [`Future`] behind a `P` pointer type.
+///
+/// # Safety
+///
+/// Only valid pointers of the same original smart-pointer type `P` must be passed to the returned
+/// closure otherwise causing undefined behaviour.
 pub const unsafe fn fn_poll_unforgotten<P, O>(
 ) -> impl Fn(*mut (), &mut task::Context<'_>) -> task::Poll<O>
        + Copy
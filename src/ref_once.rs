@@ -1,7 +1,14 @@
 use core::borrow::{Borrow, BorrowMut};
+use core::future::Future;
 use core::mem;
 use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
 use core::ptr;
+use core::task;
+
+mod pool;
+
+pub use pool::{PooledRefOnce, SlotPool};
 
 #[repr(transparent)]
 pub(crate) struct Once<T: ?Sized>(mem::ManuallyDrop<T>);
@@ -19,10 +26,66 @@ impl<'a, T> RefOnce<'a, T> {
         }
     }
 
+    /// Initialize `slot` in place with `init`, usually by the means of
+    /// [`mem::MaybeUninit::write`], without moving the value through the
+    /// stack of the caller.
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize `slot` if it returns without panicking.
+    pub unsafe fn from_uninit_with<F>(slot: &'a mut mem::MaybeUninit<T>, init: F) -> Self
+    where
+        F: FnOnce(&mut mem::MaybeUninit<T>),
+    {
+        init(&mut *slot);
+        RefOnce {
+            slot: unsafe { mem::transmute::<&'a mut mem::MaybeUninit<T>, &'a mut Once<T>>(slot) },
+        }
+    }
+
     pub fn into_inner(this: Self) -> T {
         let mut this = mem::ManuallyDrop::new(this);
         unsafe { mem::ManuallyDrop::take(&mut this.slot.0) }
     }
+
+    /// Convert into [`RefOnce`] of an unsized type, like a slice or a
+    /// trait object, by the means of `coerce` closure. Usually it's
+    /// enough to cast the reference into the inferred target type:
+    ///
+    /// ```
+    /// # use std::mem::MaybeUninit;
+    /// # use scope_lock::RefOnce;
+    /// let mut slots = (MaybeUninit::uninit(), MaybeUninit::uninit());
+    /// let mut x = 0;
+    /// let mut y = 0;
+    /// let fns: [RefOnce<'_, dyn FnMut(i32)>; 2] = [
+    ///     RefOnce::unsize(RefOnce::new(|i| x += i, &mut slots.0), |f| f as _),
+    ///     RefOnce::unsize(RefOnce::new(|i| y -= i, &mut slots.1), |f| f as _),
+    /// ];
+    /// for mut f in fns {
+    ///     f(1);
+    /// }
+    /// assert_eq!((x, y), (1, -1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if returned reference does not point at the whole object.
+    pub fn unsize<U, F>(this: Self, coerce: F) -> RefOnce<'a, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let ptr = Self::into_raw(this);
+        let unsized_ref = coerce(unsafe { &mut *ptr });
+        assert!(
+            ptr::addr_eq(unsized_ref, ptr) && mem::size_of_val(unsized_ref) == mem::size_of::<T>(),
+            "coercion returned a reference to something other than the object"
+        );
+        RefOnce {
+            slot: unsafe { &mut *(unsized_ref as *mut U as *mut Once<U>) },
+        }
+    }
 }
 
 impl<'a, T: ?Sized> RefOnce<'a, T> {
@@ -48,6 +111,35 @@ impl<'a, T: ?Sized> RefOnce<'a, T> {
     }
 }
 
+impl<'a, T: ?Sized> RefOnce<'a, T> {
+    /// Consume [`RefOnce`] without ever dropping the object, returning a
+    /// mutable reference to it for the rest of the slot's lifetime.
+    pub fn leak(this: Self) -> &'a mut T {
+        unsafe { &mut *Self::into_raw(this) }
+    }
+
+    /// Project [`RefOnce`] onto a part of the object, like one of its
+    /// fields. Only the projected part is going to be dropped, while the
+    /// rest of the object is leaked.
+    ///
+    /// # Safety
+    ///
+    /// Reference returned from `f` must point at a part of the object,
+    /// which is owned by it inline. For example a field of a struct is
+    /// fine, but anything behind another reference or a smart pointer
+    /// isn't, because [`RefOnce`] will drop it in place.
+    pub unsafe fn map<U, F>(this: Self, f: F) -> RefOnce<'a, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let value = Self::leak(this);
+        RefOnce {
+            slot: unsafe { &mut *(f(value) as *mut U as *mut Once<U>) },
+        }
+    }
+}
+
 impl<T: ?Sized> Deref for RefOnce<'_, T> {
     type Target = T;
 
@@ -79,3 +171,77 @@ impl<T: ?Sized> Drop for RefOnce<'_, T> {
         unsafe { mem::ManuallyDrop::drop(&mut self.slot.0) }
     }
 }
+
+/// [`RefOnce`] with a pinned object.
+///
+/// Mimicking [`Box::into_pin`] for [`RefOnce`] is unsound in general, see
+/// [`PointerPinUnforgotten`](crate::pointer_like::PointerPinUnforgotten)
+/// implementation for [`RefOnce`]. This wrapper moves this obligation to
+/// the caller of [`Self::new_unchecked`], while offering the same
+/// guarantees as `Pin<Box<T>>` afterwards.
+pub struct PinnedRefOnce<'a, T: ?Sized>(RefOnce<'a, T>);
+
+impl<'a, T: ?Sized> PinnedRefOnce<'a, T> {
+    /// Pin object behind the [`RefOnce`].
+    ///
+    /// # Safety
+    ///
+    /// Returned value must not be forgotten, that is it should be
+    /// dropped before the slot of the original [`RefOnce`] is reused or
+    /// deallocated.
+    pub unsafe fn new_unchecked(this: RefOnce<'a, T>) -> Self {
+        PinnedRefOnce(this)
+    }
+
+    pub fn as_ref(&self) -> Pin<&T> {
+        unsafe { Pin::new_unchecked(&*self.0) }
+    }
+
+    pub fn as_mut(&mut self) -> Pin<&mut T> {
+        unsafe { Pin::new_unchecked(&mut *self.0) }
+    }
+
+    /// Same as [`RefOnce::into_raw`].
+    pub fn into_raw(this: Self) -> *mut T {
+        RefOnce::into_raw(this.0)
+    }
+
+    /// Same as [`RefOnce::from_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned from [`Self::into_raw`]. New
+    /// lifetime argument `'a` of [`PinnedRefOnce`] should not outlive old
+    /// lifetime not to cause any undefined behaviour.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        PinnedRefOnce(unsafe { RefOnce::from_raw(ptr) })
+    }
+}
+
+impl<'a, T: Unpin> PinnedRefOnce<'a, T> {
+    pub fn into_inner(this: Self) -> T {
+        RefOnce::into_inner(this.0)
+    }
+}
+
+impl<T: ?Sized> Deref for PinnedRefOnce<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: ?Sized + Unpin> DerefMut for PinnedRefOnce<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: ?Sized + Future> Future for PinnedRefOnce<'_, T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        self.get_mut().as_mut().poll(cx)
+    }
+}
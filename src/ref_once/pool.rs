@@ -0,0 +1,172 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Fixed set of `N` slots handing out [`PooledRefOnce`] values.
+///
+/// Unlike a single [`core::mem::MaybeUninit`] slot used by
+/// [`RefOnce`](crate::RefOnce), a slot of the pool becomes available
+/// again once its [`PooledRefOnce`] is dropped or consumed, so the pool
+/// can serve any number of values over its lifetime without allocating.
+///
+/// Values are handed out as [`PooledRefOnce`] rather than
+/// [`RefOnce`](crate::RefOnce), since the latter only drops its value
+/// and has no way to mark the slot as free again. [`PooledRefOnce`]
+/// implements the same pointer traits, so it's accepted by
+/// [`Extender::fn_once`](crate::Extender::fn_once) and the rest of
+/// extension methods just like [`RefOnce`](crate::RefOnce):
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+///
+/// let pool = scope_lock::SlotPool::<_, 4>::new();
+/// let sum = AtomicUsize::new(0);
+/// scope_lock::lock_scope(|e| {
+///     for i in 0..4 {
+///         let sum = &sum;
+///         let f = pool.try_insert(move |()| sum.fetch_add(i, Ordering::Relaxed));
+///         let f = e.fn_once(f.ok().unwrap());
+///         thread::spawn(move || f(()));
+///     }
+/// });
+/// assert_eq!(sum.into_inner(), 6);
+/// ```
+pub struct SlotPool<T, const N: usize> {
+    slots: [Slot<T>; N],
+}
+
+// Value must be the first field, so that pointer to a slot is also a
+// pointer to its value.
+#[repr(C)]
+struct Slot<T> {
+    value: UnsafeCell<mem::MaybeUninit<T>>,
+    occupied: AtomicBool,
+}
+
+impl<T> Slot<T> {
+    const fn new() -> Self {
+        Slot {
+            value: UnsafeCell::new(mem::MaybeUninit::uninit()),
+            occupied: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<T, const N: usize> SlotPool<T, N> {
+    pub const fn new() -> Self {
+        SlotPool {
+            slots: [const { Slot::new() }; N],
+        }
+    }
+
+    /// Move `value` into a free slot. Returns `value` back if every slot
+    /// is occupied.
+    pub fn try_insert(&self, value: T) -> Result<PooledRefOnce<'_, T>, T> {
+        let Some(slot) = self.slots.iter().find(|slot| {
+            slot.occupied
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        }) else {
+            return Err(value);
+        };
+        unsafe { (*slot.value.get()).write(value) };
+        Ok(PooledRefOnce {
+            slot: ptr::NonNull::from(slot),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T, const N: usize> Default for SlotPool<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<T: Send, const N: usize> Sync for SlotPool<T, N> {}
+
+/// Same as [`RefOnce`](crate::RefOnce), but releases its slot back into
+/// the [`SlotPool`] once the value is dropped or moved out.
+pub struct PooledRefOnce<'a, T> {
+    slot: ptr::NonNull<Slot<T>>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Send> Send for PooledRefOnce<'_, T> {}
+unsafe impl<T: Sync> Sync for PooledRefOnce<'_, T> {}
+
+impl<'a, T> PooledRefOnce<'a, T> {
+    pub fn into_inner(this: Self) -> T {
+        let this = mem::ManuallyDrop::new(this);
+        let slot = unsafe { this.slot.as_ref() };
+        let value = unsafe { (*slot.value.get()).assume_init_read() };
+        slot.occupied.store(false, Ordering::Release);
+        value
+    }
+
+    /// Essentially leaks object as a pointer until the original
+    /// [`PooledRefOnce`] is restored via [`Self::from_raw`].
+    pub fn into_raw(this: Self) -> *mut T {
+        let this = mem::ManuallyDrop::new(this);
+        this.slot.as_ptr() as *mut T
+    }
+
+    /// Convert pointer returned from [`Self::into_raw`] back into
+    /// [`PooledRefOnce`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned from [`Self::into_raw`]. New
+    /// lifetime argument `'a` of [`PooledRefOnce`] should not outlive old
+    /// lifetime not to cause any undefined behaviour.
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        PooledRefOnce {
+            slot: unsafe { ptr::NonNull::new_unchecked(ptr as *mut Slot<T>) },
+            _marker: PhantomData,
+        }
+    }
+
+    fn value_ptr(&self) -> *mut T {
+        unsafe { self.slot.as_ref() }.value.get() as *mut T
+    }
+}
+
+impl<T> Deref for PooledRefOnce<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.value_ptr() }
+    }
+}
+
+impl<T> DerefMut for PooledRefOnce<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.value_ptr() }
+    }
+}
+
+impl<T> Borrow<T> for PooledRefOnce<'_, T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T> BorrowMut<T> for PooledRefOnce<'_, T> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T> Drop for PooledRefOnce<'_, T> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.value_ptr()) };
+        unsafe { self.slot.as_ref() }
+            .occupied
+            .store(false, Ordering::Release);
+    }
+}
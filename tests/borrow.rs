@@ -0,0 +1,18 @@
+use std::thread;
+
+#[test]
+fn shared_and_exclusive() {
+    let config = String::from("config");
+    let mut counters = vec![0; 4];
+
+    scope_lock::lock_scope(|e| {
+        let config = e.shared(config.as_str());
+        for counter in counters.iter_mut() {
+            let config = config.clone();
+            let mut counter = e.exclusive(counter);
+            thread::spawn(move || *counter += config.len());
+        }
+    });
+
+    assert_eq!(counters, [6; 4]);
+}
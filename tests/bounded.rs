@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use scope_lock::LimitReached;
+
+#[test]
+fn extension_blocks_at_limit() {
+    let alive = AtomicUsize::new(0);
+    let max_alive = AtomicUsize::new(0);
+
+    scope_lock::lock_scope_bounded(2, |e| {
+        for _ in 0..8 {
+            let f = e.fn_once(Box::new(|()| {
+                thread::sleep(Duration::from_millis(5));
+                alive.fetch_sub(1, Ordering::Relaxed);
+            }));
+            let now = alive.fetch_add(1, Ordering::Relaxed) + 1;
+            max_alive.fetch_max(now, Ordering::Relaxed);
+            thread::spawn(move || f(()));
+        }
+    });
+
+    assert!(max_alive.into_inner() <= 2);
+}
+
+#[test]
+fn try_reserve_at_limit() {
+    let data = [1, 2, 3];
+
+    scope_lock::lock_scope_bounded(1, |e| {
+        let f = e.try_reserve().unwrap().fn_(Box::new(|()| data.len()));
+        assert_eq!(e.try_reserve().err(), Some(LimitReached));
+        // clones aren't blocked, but count toward the limit
+        let g = f.clone();
+        drop(f);
+        assert!(e.try_reserve().is_err());
+        assert_eq!(g(()), 3);
+        drop(g);
+        let reservation = e.try_reserve().unwrap();
+        drop(reservation);
+        assert!(e.try_reserve().is_ok());
+    });
+}
+
+#[test]
+fn reserve_async_waits_for_release() {
+    let mut x = 0;
+
+    scope_lock::lock_scope_bounded(1, |e| {
+        let f = e.fn_once(Box::new(|()| thread::sleep(Duration::from_millis(20))));
+        thread::spawn(move || f(()));
+        let reservation = futures::executor::block_on(e.reserve_async());
+        let mut f = reservation.fn_mut(Box::new(|()| x += 1));
+        thread::spawn(move || f(()));
+    });
+
+    assert_eq!(x, 1);
+}
+
+#[test]
+fn run_proxy_calls_while_blocked() {
+    let counter = std::cell::Cell::new(0);
+
+    scope_lock::lock_scope_bounded(1, |e| {
+        let f = e.proxy(Box::new(|()| counter.set(counter.get() + 1)));
+        thread::spawn(move || f.call(()));
+        // the proxy is called and dropped by the time it's reserved
+        let reservation = e.reserve();
+        assert_eq!(counter.get(), 1);
+        drop(reservation);
+    });
+}
@@ -0,0 +1,49 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task;
+use std::thread;
+
+#[test]
+fn boxed_closures() {
+    let counter = AtomicUsize::new(0);
+    let mut calls = 0;
+
+    scope_lock::lock_scope(|e| {
+        let jobs: Vec<Box<dyn FnOnce(usize) + Send + Sync>> = vec![
+            e.boxed_fn_once(|i| {
+                counter.fetch_add(i, Ordering::Relaxed);
+            }),
+            {
+                let mut f = e.boxed_fn_mut(|i| calls += i);
+                Box::new(move |i| {
+                    f(i);
+                    f(i);
+                })
+            },
+            {
+                let f = e.boxed_fn(|i| counter.fetch_add(i, Ordering::Relaxed));
+                Box::new(move |i| {
+                    f(i);
+                })
+            },
+        ];
+        for (i, job) in jobs.into_iter().enumerate() {
+            thread::spawn(move || job(i + 1));
+        }
+    });
+
+    assert_eq!(counter.into_inner(), 4);
+    assert_eq!(calls, 4);
+}
+
+#[test]
+fn boxed_future() {
+    let data = [1, 2, 3];
+    scope_lock::lock_scope(|e| {
+        let mut fut: Pin<Box<dyn Future<Output = i32> + Send>> =
+            e.boxed_future(async { data.iter().sum() });
+        let mut cx = task::Context::from_waker(task::Waker::noop());
+        assert_eq!(fut.as_mut().poll(&mut cx), task::Poll::Ready(6));
+    });
+}
@@ -0,0 +1,81 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use scope_lock::RefOnce;
+
+#[test]
+fn fan_out() {
+    let calls = AtomicUsize::new(0);
+    let f = |i: usize| {
+        calls.fetch_add(i, Ordering::Relaxed);
+    };
+
+    scope_lock::lock_scope(|e| {
+        let f = e.fn_(&f);
+        for i in 1..=4 {
+            let f = f.clone();
+            thread::spawn(move || f(i));
+        }
+    });
+
+    assert_eq!(calls.into_inner(), 10);
+}
+
+#[test]
+fn dropped_with_last_clone() {
+    struct Guard<'a>(&'a Mutex<Vec<&'static str>>);
+
+    impl Guard<'_> {
+        fn push(&self, s: &'static str) {
+            self.0.lock().unwrap().push(s);
+        }
+    }
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.push("dropped");
+        }
+    }
+
+    let log = Mutex::new(Vec::new());
+    let mut slot = std::mem::MaybeUninit::uninit();
+    let guard = Guard(&log);
+    let f = move |s| guard.push(s);
+
+    scope_lock::lock_scope(|e| {
+        let first = e.fn_(RefOnce::new(f, &mut slot));
+        let second = first.clone();
+        let third = second.clone();
+        drop(first);
+        second("second");
+        drop(second);
+        thread::spawn(move || third("third"));
+    });
+
+    assert_eq!(log.into_inner().unwrap(), ["second", "third", "dropped"]);
+}
+
+#[test]
+fn concurrent_clones() {
+    let calls = AtomicUsize::new(0);
+    let f = |()| {
+        calls.fetch_add(1, Ordering::Relaxed);
+    };
+
+    scope_lock::lock_scope(|e| {
+        let f = e.fn_(Box::new(f));
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let clones = [f.clone(), f.clone()];
+                    clones.into_iter().for_each(|f| {
+                        thread::spawn(move || f(()));
+                    });
+                });
+            }
+        });
+    });
+
+    assert_eq!(calls.into_inner(), 8);
+}
@@ -0,0 +1,13 @@
+use std::thread;
+
+use scope_lock::Extender;
+
+/// Spawn a thread running borrowing closure, like [`thread::Scope::spawn`].
+pub fn spawn<'scope, F, T>(e: &'scope Extender<'scope, '_>, f: F) -> thread::JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'scope,
+    T: Send + 'static,
+{
+    let f = e.fn_once(Box::new(move |()| f()));
+    thread::spawn(move || f(()))
+}
@@ -0,0 +1,100 @@
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+struct Task<'a> {
+    by_value: &'a AtomicUsize,
+    by_ref: &'a AtomicUsize,
+}
+
+impl Task<'_> {
+    fn by_value(self) {
+        self.by_value.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn by_ref(&self) {
+        self.by_ref.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn unique_arc_by_value() {
+    let by_value = AtomicUsize::new(0);
+    let by_ref = AtomicUsize::new(0);
+    let task = Task {
+        by_value: &by_value,
+        by_ref: &by_ref,
+    };
+    // `FnOnce` only, since the task is moved out
+    let f = Arc::new(move |()| task.by_value());
+
+    scope_lock::lock_scope(|e| {
+        let f = e.fn_once_shared(f);
+        thread::spawn(move || assert_eq!(f(()), Some(())));
+    });
+
+    assert_eq!(by_value.into_inner(), 1);
+    assert_eq!(by_ref.into_inner(), 0);
+}
+
+#[test]
+fn called_once_by_last_owner() {
+    let calls = AtomicUsize::new(0);
+    let barrier = Barrier::new(2);
+
+    for _ in 0..32 {
+        let token = Box::new(());
+        let calls = &calls;
+        let f = Arc::new(move |()| {
+            drop(token);
+            calls.fetch_add(1, Ordering::Relaxed);
+        });
+        let called = scope_lock::lock_scope(|e| {
+            thread::scope(|s| {
+                let handles = [e.fn_once_shared(f.clone()), e.fn_once_shared(f)].map(|f| {
+                    let barrier = &barrier;
+                    s.spawn(move || {
+                        barrier.wait();
+                        f(()).is_some()
+                    })
+                });
+                handles.map(|h| h.join().unwrap())
+            })
+        });
+        assert_eq!(called.iter().filter(|&&called| called).count(), 1);
+    }
+
+    assert_eq!(calls.into_inner(), 32);
+}
+
+#[test]
+fn shared_arc_falls_back_to_ref() {
+    let by_value = AtomicUsize::new(0);
+    let by_ref = AtomicUsize::new(0);
+    let task = Task {
+        by_value: &by_value,
+        by_ref: &by_ref,
+    };
+    let f = Arc::new(move |()| task.by_ref());
+
+    scope_lock::lock_scope(|e| {
+        let first = e.fn_once_shared_or_ref(f.clone());
+        let second = e.fn_once_shared_or_ref(f);
+        first(());
+        second(());
+    });
+
+    assert_eq!(by_value.into_inner(), 0);
+    assert_eq!(by_ref.into_inner(), 2);
+}
+
+#[test]
+fn moves_out_of_unique_rc() {
+    let data = vec![1, 2, 3];
+    let f = Rc::new(move |()| data);
+    let extended = unsafe { scope_lock::extend_fn_once_shared_unchecked(f.clone()) };
+    assert_eq!(Rc::strong_count(&f), 2);
+    drop(f);
+    assert_eq!(extended(()), Some(vec![1, 2, 3]));
+}
@@ -0,0 +1,101 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use common::spawn;
+
+#[test]
+fn phased_work() {
+    let mut data = vec![1, 2, 3, 4];
+
+    scope_lock::lock_scope(|e| {
+        for i in 0..3 {
+            e.group(|g| {
+                for chunk in data.chunks_mut(2) {
+                    spawn(g, move || {
+                        thread::sleep(Duration::from_millis(10));
+                        chunk.iter_mut().for_each(|x| *x *= 2);
+                    });
+                }
+            });
+            // mutable reuse in between
+            data.push(i);
+        }
+    });
+
+    assert_eq!(data, [8, 16, 24, 32, 0, 2, 2]);
+}
+
+#[test]
+fn borrow_scope_locals() {
+    scope_lock::lock_scope(|e| {
+        let a = [1, 2, 3];
+        let len = e.group(|g| spawn(g, || a.len()));
+        assert_eq!(len.join().unwrap(), 3);
+    });
+}
+
+#[test]
+fn wait_within_group() {
+    let counter = AtomicUsize::new(0);
+
+    scope_lock::lock_scope(|e| {
+        e.group(|g| {
+            for _ in 0..2 {
+                spawn(g, || {
+                    thread::sleep(Duration::from_millis(20));
+                    counter.fetch_add(1, Ordering::Relaxed);
+                });
+            }
+            g.wait();
+            assert_eq!(counter.load(Ordering::Relaxed), 2);
+            spawn(g, || counter.fetch_add(1, Ordering::Relaxed));
+        });
+        assert_eq!(counter.load(Ordering::Relaxed), 3);
+    });
+}
+
+#[test]
+fn parent_waits_for_group_in_extended() {
+    let data = [1, 2, 3];
+    let log = Mutex::new(Vec::new());
+
+    scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            e.group(|g| {
+                spawn(g, || {
+                    thread::sleep(Duration::from_millis(50));
+                    log.lock().unwrap().push(data.len());
+                });
+            });
+            log.lock().unwrap().push(0);
+        });
+    });
+
+    assert_eq!(log.into_inner().unwrap(), [3, 0]);
+}
+
+#[test]
+fn wait_revokes_weak() {
+    let data = [1, 2, 3];
+
+    scope_lock::lock_scope(|e| {
+        e.group(|g| {
+            let f = g.fn_weak(Box::new(|i| data[i]));
+            assert_eq!(f.call(1), Some(2));
+            let handle = thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                f.call(0)
+            });
+            g.wait();
+            assert_eq!(handle.join().unwrap(), None);
+            let f = g.fn_weak(Box::new(|i: usize| data[i]));
+            assert_eq!(f.call(2), Some(3));
+            g.wait();
+            assert!(f.is_revoked());
+        });
+    });
+}
@@ -0,0 +1,67 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use scope_lock::{InlineFn, InlineFnMut, InlineFnOnce};
+
+#[test]
+fn inline_closures() {
+    let counter = AtomicUsize::new(0);
+    let mut calls = 0;
+    let data = [1, 2, 3];
+
+    scope_lock::lock_scope(|e| {
+        let f: InlineFn<usize, usize, 2> = e.fn_inline(|i| counter.fetch_add(i, Ordering::Relaxed));
+        let mut f_mut: InlineFnMut<(), ()> = e.fn_mut_inline(|()| calls += data.len());
+        let f_once: InlineFnOnce<(), usize> = e.fn_once_inline(|()| data.iter().sum());
+
+        thread::spawn(move || f(1));
+        thread::spawn(move || {
+            f_mut(());
+            f_mut(());
+        });
+        thread::spawn(move || assert_eq!(f_once.call_once(()), 6));
+    });
+
+    assert_eq!(counter.into_inner(), 1);
+    assert_eq!(calls, 6);
+}
+
+#[test]
+fn drop_without_call() {
+    struct Guard<'a>(&'a Mutex<usize>);
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            *self.0.lock().unwrap() += 1;
+        }
+    }
+
+    let dropped = Mutex::new(0);
+
+    scope_lock::lock_scope(|e| {
+        let guard = Guard(&dropped);
+        let f: InlineFnOnce<(), ()> = e.fn_once_inline(move |()| drop(guard));
+        thread::spawn(move || drop(f));
+
+        let guard = Guard(&dropped);
+        let f: InlineFn<(), ()> = e.fn_inline(move |()| {
+            let _ = &guard;
+        });
+        drop(f);
+    });
+
+    assert_eq!(dropped.into_inner().unwrap(), 2);
+}
+
+#[test]
+fn interior_mutability() {
+    scope_lock::lock_scope(|e| {
+        // atomic is stored within the inline buffer itself
+        let calls = AtomicUsize::new(0);
+        let f: InlineFn<(), usize, 1> =
+            e.fn_inline(move |()| calls.fetch_add(1, Ordering::Relaxed) + 1);
+        assert_eq!(f(()), 1);
+        assert_eq!(f(()), 2);
+    });
+}
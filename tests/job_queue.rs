@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+
+use scope_lock::{Job, SlotPool};
+
+fn worker_pool(threads: usize) -> (mpsc::Sender<Job>, Vec<thread::JoinHandle<()>>) {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let workers = (0..threads)
+        .map(|_| {
+            let receiver = receiver.clone();
+            thread::spawn(move || {
+                loop {
+                    // don't hold the lock while running the job
+                    let Ok(job) = receiver.lock().unwrap().recv() else {
+                        break;
+                    };
+                    job();
+                }
+            })
+        })
+        .collect();
+    (sender, workers)
+}
+
+#[test]
+fn borrowing_jobs() {
+    let (sender, workers) = worker_pool(4);
+    let results = Mutex::new(Vec::new());
+    let data: Vec<usize> = (0..16).collect();
+    let pool = SlotPool::<_, 16>::new();
+
+    let sender = scope_lock::lock_scope(|e| {
+        let sender = e.job_queue(sender);
+        for chunk in data.chunks(4) {
+            let results = &results;
+            let job = pool.try_insert(move |()| {
+                results.lock().unwrap().push(chunk.iter().sum::<usize>());
+            });
+            sender
+                .send(job.unwrap_or_else(|_| panic!("pool is full")))
+                .unwrap();
+        }
+        sender.into_queue()
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort();
+    assert_eq!(results, [6, 22, 38, 54]);
+
+    drop(sender);
+    workers.into_iter().for_each(|w| w.join().unwrap());
+}
+
+#[test]
+fn pooled_jobs_reuse_slots() {
+    // jobs are run by hand, so that the slot's state is known at each step
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let sum = AtomicUsize::new(0);
+    let pool = SlotPool::<_, 1>::new();
+    let job = |i| {
+        let sum = &sum;
+        move |()| {
+            sum.fetch_add(i, Ordering::Relaxed);
+        }
+    };
+
+    scope_lock::lock_scope(|e| {
+        let sender = e.job_queue(sender);
+        for i in 1..=4 {
+            sender.send_pooled(&pool, job(i)).unwrap();
+            // occupied by the pending job
+            assert!(pool.try_insert(job(0)).is_err());
+            receiver.recv().unwrap()();
+            // released once the job is run
+            assert!(pool.try_insert(job(0)).is_ok());
+        }
+    });
+
+    assert_eq!(sum.into_inner(), 10);
+}
+
+#[test]
+fn dropped_on_disconnect() {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    drop(receiver);
+    let mut called = false;
+
+    scope_lock::lock_scope(|e| {
+        let sender = e.job_queue(sender);
+        assert!(sender.send(Box::new(|()| called = true)).is_err());
+    });
+
+    assert!(!called);
+}
@@ -0,0 +1,21 @@
+use std::thread;
+
+struct SetOnDrop<'a>(&'a mut bool);
+
+impl Drop for SetOnDrop<'_> {
+    fn drop(&mut self) {
+        *self.0 = true;
+    }
+}
+
+#[test]
+fn guard_dropped_on_another_thread() {
+    let mut dropped = false;
+
+    scope_lock::lock_scope(|e| {
+        let guard = e.keep_alive(Box::new(SetOnDrop(&mut dropped)));
+        thread::spawn(move || drop(guard));
+    });
+
+    assert!(dropped);
+}
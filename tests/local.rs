@@ -0,0 +1,70 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+thread_local! {
+    static EVENT_LOOP: RefCell<Vec<Box<dyn FnOnce()>>> = RefCell::new(Vec::new());
+}
+
+fn queue(f: impl FnOnce() + 'static) {
+    EVENT_LOOP.with_borrow_mut(|q| q.push(Box::new(f)));
+}
+
+fn run_queue() {
+    while let Some(f) = EVENT_LOOP.with_borrow_mut(|q| q.pop()) {
+        f();
+    }
+}
+
+#[test]
+fn event_loop() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let calls = Cell::new(0);
+
+    scope_lock::lock_scope(|e| {
+        let f = e.fn_local(Box::new(|s| log.borrow_mut().push(s)));
+        let mut count = e.fn_mut_local(Box::new(|()| calls.set(calls.get() + 1)));
+        let log = Rc::clone(&log);
+        let once = e.fn_once_local(Box::new(move |()| log.borrow_mut().push("once")));
+
+        let g = f.clone();
+        queue(move || f("first"));
+        queue(move || g("second"));
+        queue(move || {
+            count(());
+            count(());
+        });
+        queue(move || once.call_once(()));
+        run_queue();
+    });
+
+    let mut log = Rc::try_unwrap(log).unwrap().into_inner();
+    log.sort();
+    assert_eq!(log, ["first", "once", "second"]);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn local_set() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let local = tokio::task::LocalSet::new();
+    let counter = Rc::new(Cell::new(0));
+
+    local.block_on(&rt, async {
+        unsafe {
+            scope_lock::lock_scope_async(|e| {
+                for i in 1..=3 {
+                    let counter = &counter;
+                    tokio::task::spawn_local(e.future_local(Box::pin(async move {
+                        tokio::task::yield_now().await;
+                        counter.set(counter.get() + i);
+                    })));
+                }
+            })
+        }
+        .await;
+    });
+
+    assert_eq!(counter.get(), 6);
+}
@@ -0,0 +1,97 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+
+async fn yield_now() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+        if yielded {
+            return std::task::Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+    })
+    .await
+}
+
+#[test]
+fn drives_local_pool() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let counter = Cell::new(0);
+
+    scope_lock::lock_scope_local(
+        move || pool.try_run_one(),
+        |e| {
+            for i in 1..=3 {
+                let counter = &counter;
+                let fut = e.future_local(Box::pin(async move {
+                    yield_now().await;
+                    counter.set(counter.get() + i);
+                }));
+                spawner.spawn_local(fut).unwrap();
+            }
+        },
+    );
+
+    assert_eq!(counter.get(), 6);
+}
+
+#[test]
+fn blocks_for_other_threads() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let (sender, receiver) = oneshot::channel();
+    let received = Cell::new(None);
+
+    scope_lock::lock_scope_local(
+        move || pool.try_run_one(),
+        |e| {
+            let received = &received;
+            let fut = e.future_local(Box::pin(async move {
+                received.set(receiver.await.ok());
+            }));
+            spawner.spawn_local(fut).unwrap();
+            // executor runs dry before the thread is done
+            let f = e.fn_once(Box::new(move |()| {
+                thread::sleep(Duration::from_millis(20));
+                sender.send(5).unwrap();
+            }));
+            thread::spawn(move || f(()));
+        },
+    );
+
+    assert_eq!(received.get(), Some(5));
+}
+
+#[test]
+fn parks_until_woken_by_other_threads() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let (sender, receiver) = oneshot::channel();
+    let received = Cell::new(None);
+
+    // only a local extended object is alive, but it's woken by a thread
+    // outside of the scope
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        sender.send(5).unwrap();
+    });
+    scope_lock::lock_scope_local(
+        move || pool.try_run_one(),
+        |e| {
+            let received = &received;
+            let fut = e.future_local(Box::pin(async move {
+                received.set(receiver.await.ok());
+            }));
+            spawner.spawn_local(fut).unwrap();
+        },
+    );
+
+    assert_eq!(received.get(), Some(5));
+}
@@ -0,0 +1,139 @@
+#![cfg(loom)]
+
+use std::future::Future;
+use std::pin::pin;
+use std::task;
+
+use loom::sync::atomic::{AtomicUsize, Ordering};
+use loom::thread;
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let mut cx = task::Context::from_waker(task::Waker::noop());
+    loop {
+        if let task::Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::yield_now();
+    }
+}
+
+#[test]
+fn release_before_scope_exit() {
+    loom::model(|| {
+        let mut x = 0;
+        scope_lock::lock_scope(|e| {
+            let mut f = e.fn_mut(Box::new(|()| x += 1));
+            thread::spawn(move || f(()));
+        });
+        assert_eq!(x, 1);
+    });
+}
+
+#[test]
+fn concurrent_releases_wake_waiting_scope() {
+    loom::model(|| {
+        let counter = AtomicUsize::new(0);
+        scope_lock::lock_scope(|e| {
+            for _ in 0..2 {
+                let f = e.fn_(Box::new(|()| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }));
+                thread::spawn(move || f(()));
+            }
+        });
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+    });
+}
+
+#[test]
+fn acquire_during_scope_exit() {
+    loom::model(|| {
+        let counter = AtomicUsize::new(0);
+        scope_lock::lock_scope(|e| {
+            let counter = &counter;
+            let f = e.fn_once(Box::new(move |()| {
+                let f = e.fn_once(Box::new(move |()| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }));
+                thread::spawn(move || f(()));
+            }));
+            thread::spawn(move || f(()));
+        });
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[test]
+fn closure_async_boxed() {
+    loom::model(|| {
+        let a = 37;
+        let mut sum = 0;
+        scope_lock::lock_scope(|e| {
+            let mut f = e.fn_mut(Box::new(|b| e.future(Box::new(async move { a + b }))));
+            let sum = e.exclusive(&mut sum);
+            thread::spawn(move || {
+                let mut sum = sum;
+                *sum = block_on(f(5));
+            });
+        });
+        assert_eq!(sum, 42);
+    });
+}
+
+#[test]
+fn async_scope_wakeup() {
+    loom::model(|| {
+        let mut x = 0;
+        block_on(unsafe {
+            scope_lock::lock_scope_async(|e| {
+                let mut f = e.fn_mut(Box::new(|()| x += 1));
+                thread::spawn(move || f(()));
+            })
+        });
+        assert_eq!(x, 1);
+    });
+}
+
+#[test]
+fn weak_call_during_scope_exit() {
+    loom::model(|| {
+        let data = [1, 2, 3];
+        let handle = scope_lock::lock_scope(|e| {
+            let f = e.fn_weak(Box::new(|()| data.iter().sum::<i32>()));
+            thread::spawn(move || {
+                let result = f.call(());
+                assert!(result.is_none() || result == Some(6));
+            })
+        });
+        handle.join().unwrap();
+    });
+}
+
+#[test]
+fn proxy_call_during_scope_exit() {
+    loom::model(|| {
+        let x = std::cell::Cell::new(0);
+        scope_lock::lock_scope(|e| {
+            let f = e.proxy(Box::new(|()| x.set(x.get() + 1)));
+            thread::spawn(move || f.call(()));
+        });
+        assert_eq!(x.get(), 1);
+    });
+}
+
+#[test]
+fn acquire_blocked_at_limit() {
+    loom::model(|| {
+        let counter = AtomicUsize::new(0);
+        scope_lock::lock_scope_bounded(1, |e| {
+            for _ in 0..2 {
+                let f = e.fn_(Box::new(|()| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }));
+                thread::spawn(move || f(()));
+            }
+        });
+        assert_eq!(counter.load(Ordering::Relaxed), 2);
+    });
+}
@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use scope_lock::{ExtendedFn, ExtendedFnMut, ExtendedFnOnce, ExtendedFnUnsync, ExtendedFuture};
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+fn assert_send<T: Send>(_: &T) {}
+
+enum Job<'scope> {
+    Once(ExtendedFnOnce<'scope, (), usize>),
+    Mut(ExtendedFnMut<'scope, (), usize>),
+    Shared(ExtendedFn<'scope, (), usize>),
+}
+
+impl Job<'_> {
+    fn run(self) -> usize {
+        match self {
+            Job::Once(f) => f.call_once(()),
+            Job::Mut(mut f) => f(()) + f(()),
+            Job::Shared(f) => f(()),
+        }
+    }
+}
+
+#[test]
+fn store_in_enum() {
+    let counter = AtomicUsize::new(0);
+    let mut calls = 0;
+    let once = || counter.fetch_add(1, Ordering::Relaxed) + 1;
+    let mut mutable = |()| {
+        calls += 1;
+        calls
+    };
+    let shared = |()| counter.load(Ordering::Relaxed);
+
+    let results = scope_lock::lock_scope(|e| {
+        let jobs = [
+            Job::Once(ExtendedFnOnce::new(e, Box::new(move |()| once()))),
+            Job::Mut(ExtendedFnMut::new(e, &mut mutable)),
+            Job::Shared(ExtendedFn::new(e, &shared)),
+        ];
+        jobs.iter().for_each(assert_send_sync);
+        thread::scope(|s| {
+            let handles = jobs.map(|job| s.spawn(move || job.run()));
+            handles.map(|h| h.join().unwrap())
+        })
+    });
+
+    assert_eq!(results[0], 1);
+    assert_eq!(results[1], 3);
+}
+
+#[test]
+fn into_static() {
+    let data = [1, 2, 3];
+    let sum = |()| data.iter().sum::<i32>();
+
+    scope_lock::lock_scope(|e| {
+        let f = ExtendedFn::new(e, &sum).into_static();
+        thread::spawn(move || assert_eq!(f(()), 6));
+    });
+}
+
+#[test]
+fn auto_traits() {
+    let f = |()| ();
+    let fut = async {};
+
+    scope_lock::lock_scope(|e| {
+        let unsync: ExtendedFnUnsync<(), ()> = ExtendedFnUnsync::new(e, &f);
+        assert_send(&unsync);
+        let fut: ExtendedFuture<()> = ExtendedFuture::new(e, Box::pin(fut));
+        assert_send_sync(&fut);
+    });
+}
@@ -0,0 +1,47 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use common::spawn;
+
+#[test]
+fn nested_scope_waits_before_returning() {
+    let log = Mutex::new(Vec::new());
+
+    scope_lock::lock_scope(|e| {
+        let log = e.shared(&log);
+        e.nested(|nested| {
+            let log = log.clone();
+            // re-extend parent's extended object
+            spawn(nested, move || {
+                thread::sleep(Duration::from_millis(50));
+                log.lock().unwrap().push("nested");
+            });
+        });
+        log.lock().unwrap().push("after nested");
+    });
+
+    assert_eq!(log.into_inner().unwrap(), ["nested", "after nested"]);
+}
+
+#[test]
+fn parent_waits_for_nested_in_extended() {
+    let data = [1, 2, 3];
+    let log = Mutex::new(Vec::new());
+
+    scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            e.nested(|nested| {
+                spawn(nested, || {
+                    thread::sleep(Duration::from_millis(50));
+                    log.lock().unwrap().push(data.len());
+                });
+            });
+            log.lock().unwrap().push(0);
+        });
+    });
+
+    assert_eq!(log.into_inner().unwrap(), [3, 0]);
+}
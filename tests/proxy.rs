@@ -0,0 +1,169 @@
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+#[test]
+fn runs_on_scope_thread() {
+    let scope_thread = thread::current().id();
+    let calls = RefCell::new(Vec::new());
+
+    scope_lock::lock_scope(|e| {
+        let f = e.proxy(Box::new(|i| {
+            assert_eq!(thread::current().id(), scope_thread);
+            calls.borrow_mut().push(i);
+            i * 2
+        }));
+        for i in 0..4 {
+            let f = f.clone();
+            thread::spawn(move || assert_eq!(f.call(i), i * 2));
+        }
+        assert_eq!(f.call(10), 20);
+    });
+
+    let mut calls = calls.into_inner();
+    calls.sort();
+    assert_eq!(calls, [0, 1, 2, 3, 10]);
+}
+
+#[test]
+fn pump_within_scope() {
+    let counter = Rc::new(Cell::new(0));
+    let done = AtomicBool::new(false);
+
+    scope_lock::lock_scope(|e| {
+        let counter = counter.clone();
+        let f = e.proxy(Box::new(move |()| counter.set(counter.get() + 1)));
+        let done_flag = e.shared(&done);
+        thread::spawn(move || {
+            f.call(());
+            f.call(());
+            // posts a job as well
+            drop(f);
+            done_flag.store(true, Ordering::Release);
+        });
+        let mut pumped = 0;
+        while !done.load(Ordering::Acquire) {
+            pumped += e.pump();
+            thread::yield_now();
+        }
+        pumped += e.pump();
+        assert_eq!(pumped, 3);
+    });
+
+    assert_eq!(counter.get(), 2);
+}
+
+#[test]
+fn panic_propagated_to_caller() {
+    let scope_thread = thread::current().id();
+
+    let handle = scope_lock::lock_scope(|e| {
+        let f = e.proxy(Box::new(|()| {
+            assert_eq!(thread::current().id(), scope_thread);
+            panic!("expected panic")
+        }));
+        thread::spawn(move || f.call(()))
+    });
+
+    let payload = handle.join().unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"expected panic"));
+}
+
+#[test]
+fn dropped_on_scope_thread() {
+    struct Guard<'a>(&'a Cell<Option<thread::ThreadId>>);
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.0.set(Some(thread::current().id()));
+        }
+    }
+
+    let dropped_on = Cell::new(None);
+
+    scope_lock::lock_scope(|e| {
+        let guard = Guard(&dropped_on);
+        let f = e.proxy(Box::new(move |()| {
+            let _ = &guard;
+        }));
+        thread::spawn(move || drop(f)).join().unwrap();
+        assert_eq!(dropped_on.get(), None);
+    });
+
+    assert_eq!(dropped_on.get(), Some(thread::current().id()));
+}
+
+#[test]
+#[should_panic = "outside of the scope's thread"]
+fn pump_outside_of_scope_thread() {
+    scope_lock::lock_scope(|e| {
+        let _f = e.proxy(Box::new(|()| ()));
+        let result = thread::scope(|s| s.spawn(|| e.pump()).join());
+        panic::resume_unwind(result.unwrap_err());
+    });
+}
+
+#[test]
+#[should_panic = "outside of the scope's thread"]
+fn pump_without_proxies_outside_of_scope_thread() {
+    scope_lock::lock_scope(|e| {
+        assert_eq!(e.pump(), 0);
+        let result = thread::scope(|s| s.spawn(|| e.pump()).join());
+        panic::resume_unwind(result.unwrap_err());
+    });
+}
+
+#[test]
+#[should_panic = "proxy is created outside of the scope's thread"]
+fn created_outside_of_scope_thread() {
+    scope_lock::lock_scope(|e| {
+        let result = thread::scope(|s| {
+            s.spawn(|| {
+                e.proxy(Box::new(|()| ()));
+            })
+            .join()
+        });
+        panic::resume_unwind(result.unwrap_err());
+    });
+}
+
+#[test]
+#[should_panic = "proxies are unavailable"]
+fn unavailable_in_async_scope() {
+    let value = Cell::new(0);
+    futures::executor::block_on(unsafe {
+        scope_lock::lock_scope_async(|e| {
+            e.proxy(Box::new(|()| value.set(1)));
+        })
+    });
+}
+
+#[test]
+fn destructor_panic_after_wait() {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            panic!("expected panic");
+        }
+    }
+
+    let called = Cell::new(false);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        scope_lock::lock_scope(|e| {
+            let guard = Guard;
+            let f = e.proxy(Box::new(move |()| {
+                let _ = &guard;
+            }));
+            thread::spawn(move || drop(f));
+            let g = e.proxy(Box::new(|()| called.set(true)));
+            thread::spawn(move || g.call(()));
+        })
+    }));
+
+    assert!(result.is_err());
+    // scope has run the rest of calls anyway
+    assert!(called.get());
+}
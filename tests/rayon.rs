@@ -0,0 +1,53 @@
+#![cfg(feature = "rayon")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn borrow_local_vec_from_pool() {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    let data: Vec<usize> = (0..100).collect();
+    let sum = AtomicUsize::new(0);
+    let add = |i: usize| {
+        sum.fetch_add(data[i], Ordering::Relaxed);
+    };
+
+    scope_lock::lock_scope(|e| {
+        e.par_for_each_extended(Some(&pool), 0..data.len(), &add);
+    });
+
+    assert_eq!(sum.into_inner(), data.iter().sum());
+}
+
+#[test]
+fn spawn_on_global_pool() {
+    let mut data = vec![1, 2, 3];
+
+    scope_lock::lock_scope(|e| {
+        e.rayon_spawn(None, Box::new(|()| data.push(4)));
+    });
+
+    assert_eq!(data, [1, 2, 3, 4]);
+}
+
+#[test]
+fn help_within_single_thread_pool() {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    let mut data = vec![1, 2, 3];
+
+    // With `lock_scope` the only thread of the pool would block waiting
+    // for the job queued behind it.
+    pool.install(|| {
+        scope_lock::lock_scope_with_waiter(
+            || rayon::yield_now() == Some(rayon::Yield::Executed),
+            |e| e.rayon_spawn(None, Box::new(|()| data.push(4))),
+        )
+    });
+
+    assert_eq!(data, [1, 2, 3, 4]);
+}
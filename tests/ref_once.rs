@@ -0,0 +1,86 @@
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::rc::Rc;
+use std::thread;
+
+use scope_lock::{PinnedRefOnce, RefOnce};
+
+#[test]
+fn map_drops_only_projected_field() {
+    let first = Rc::new(());
+    let second = Rc::new(());
+    let mut slot = MaybeUninit::uninit();
+    let pair = RefOnce::new((Rc::clone(&first), Rc::clone(&second)), &mut slot);
+    let projected = unsafe { RefOnce::map(pair, |(a, _)| a) };
+    drop(projected);
+    assert_eq!(Rc::strong_count(&first), 1);
+    assert_eq!(Rc::strong_count(&second), 2);
+    unsafe { ptr::drop_in_place(ptr::addr_of_mut!((*slot.as_mut_ptr()).1)) };
+}
+
+#[test]
+fn from_uninit_with_writes_in_place() {
+    let mut slot = MaybeUninit::uninit();
+    let value = unsafe {
+        RefOnce::from_uninit_with(&mut slot, |slot| {
+            slot.write([7u8; 64]);
+        })
+    };
+    assert_eq!(RefOnce::into_inner(value), [7u8; 64]);
+}
+
+#[test]
+fn leak_does_not_drop() {
+    let rc = Rc::new(());
+    let mut slot = MaybeUninit::uninit();
+    let leaked = RefOnce::leak(RefOnce::new(Rc::clone(&rc), &mut slot));
+    assert!(Rc::ptr_eq(leaked, &rc));
+    assert_eq!(Rc::strong_count(&rc), 2);
+    unsafe { ptr::drop_in_place(leaked) };
+}
+
+#[test]
+fn pinned_future() {
+    let a = 37;
+    let mut slot = MaybeUninit::uninit();
+    scope_lock::lock_scope(|e| {
+        let fut = unsafe {
+            PinnedRefOnce::new_unchecked(RefOnce::new(async { assert_eq!(a, 37) }, &mut slot))
+        };
+        let mut fut = Box::pin(e.future(fut));
+        thread::spawn(move || {
+            let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+            assert!(fut.as_mut().poll(&mut cx).is_ready());
+        });
+    });
+}
+
+#[test]
+fn unsize_into_dyn_collection() {
+    let a = [1, 2, 3];
+    let mut x = 0;
+    let mut y = 0usize;
+    let mut slots = (
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+        MaybeUninit::uninit(),
+    );
+
+    scope_lock::lock_scope(|e| {
+        let fns: Vec<RefOnce<'_, dyn FnMut(usize) + Send>> = vec![
+            RefOnce::unsize(RefOnce::new(|i| x += a[i], &mut slots.0), |f| f as _),
+            RefOnce::unsize(RefOnce::new(|i| y += i, &mut slots.1), |f| f as _),
+        ];
+        let f = e.fn_once(RefOnce::new(
+            move |()| {
+                for mut f in fns {
+                    f(2);
+                }
+            },
+            &mut slots.2,
+        ));
+        thread::spawn(move || f(()));
+    });
+
+    assert_eq!((x, y), (3, 2));
+}
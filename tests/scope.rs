@@ -0,0 +1,194 @@
+//! Behaviour of [`std::thread::scope`] expected from [`scope_lock::lock_scope`].
+
+use std::panic;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use common::spawn;
+
+#[test]
+fn zero_extensions() {
+    assert_eq!(scope_lock::lock_scope(|_| 42), 42);
+}
+
+#[test]
+fn scoped_threads_in_parallel() {
+    let mut a = vec![1, 2, 3];
+    let mut x = 0;
+
+    scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            // We can borrow `a` here.
+            assert_eq!(a, [1, 2, 3]);
+        });
+        spawn(e, || {
+            // We can even mutably borrow `x` here,
+            // because no other threads are using it.
+            x += a[0] + a[2];
+        });
+    });
+
+    // After the scope, we can modify and access our variables again:
+    a.push(4);
+    assert_eq!(x, a.len());
+}
+
+#[test]
+fn many_threads() {
+    let counter = AtomicUsize::new(0);
+
+    scope_lock::lock_scope(|e| {
+        for _ in 0..100 {
+            spawn(e, || counter.fetch_add(1, Ordering::Relaxed));
+        }
+    });
+
+    assert_eq!(counter.into_inner(), 100);
+}
+
+#[test]
+fn waits_for_slow_threads() {
+    let done = AtomicBool::new(false);
+
+    scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            thread::sleep(Duration::from_millis(100));
+            done.store(true, Ordering::Relaxed);
+        });
+    });
+
+    assert!(done.into_inner());
+}
+
+#[test]
+fn results_borrowed_after_scope() {
+    let data = [1, 2, 3, 4];
+    let mut sums = [0; 2];
+
+    scope_lock::lock_scope(|e| {
+        for (sum, chunk) in sums.iter_mut().zip(data.chunks(2)) {
+            spawn(e, move || *sum = chunk.iter().sum());
+        }
+    });
+
+    assert_eq!(sums, [3, 7]);
+}
+
+#[test]
+fn join_handle_returns_result() {
+    let data = [1, 2, 3];
+
+    let sum = scope_lock::lock_scope(|e| spawn(e, || data.iter().sum::<i32>()));
+
+    assert_eq!(sum.join().unwrap(), 6);
+}
+
+#[test]
+fn nested_scopes() {
+    let outer = Mutex::new(Vec::new());
+
+    scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            let inner = AtomicUsize::new(0);
+            scope_lock::lock_scope(|e| {
+                for i in 1..=3 {
+                    let inner = &inner;
+                    spawn(e, move || inner.fetch_add(i, Ordering::Relaxed));
+                }
+            });
+            outer.lock().unwrap().push(inner.into_inner());
+        });
+        scope_lock::lock_scope(|inner| {
+            spawn(inner, || outer.lock().unwrap().push(0));
+        });
+    });
+
+    let mut outer = outer.into_inner().unwrap();
+    outer.sort();
+    assert_eq!(outer, [0, 6]);
+}
+
+#[test]
+fn spawn_from_spawned_thread() {
+    let counter = AtomicUsize::new(0);
+
+    scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            spawn(e, || counter.fetch_add(1, Ordering::Relaxed));
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+    });
+
+    assert_eq!(counter.into_inner(), 2);
+}
+
+#[test]
+fn panic_in_spawned_thread() {
+    let a = [1, 2, 3];
+
+    let handle = scope_lock::lock_scope(|e| {
+        spawn(e, || {
+            assert_eq!(a.len(), 3);
+            panic!("expected panic");
+        })
+    });
+
+    let payload = handle.join().unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"expected panic"));
+    assert_eq!(a.len(), 3);
+}
+
+#[test]
+fn panic_in_scope_closure() {
+    let done = AtomicBool::new(false);
+
+    let result = panic::catch_unwind(|| {
+        scope_lock::lock_scope(|e| {
+            spawn(e, || {
+                thread::sleep(Duration::from_millis(100));
+                done.store(true, Ordering::Relaxed);
+            });
+            panic!("expected panic");
+        })
+    });
+
+    assert!(result.is_err());
+    // Scope has waited for the spawned thread anyway
+    assert!(done.into_inner());
+}
+
+#[test]
+fn extensions_dropped_without_call() {
+    let called = AtomicBool::new(false);
+    let dropped = AtomicUsize::new(0);
+
+    struct CountDrop<'a>(&'a AtomicUsize);
+    impl Drop for CountDrop<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    scope_lock::lock_scope(|e| {
+        let guard = CountDrop(&dropped);
+        let f = e.fn_once(Box::new(|()| {
+            let _guard = guard;
+            called.store(true, Ordering::Relaxed);
+        }));
+        thread::spawn(move || drop(f));
+
+        let guard = CountDrop(&dropped);
+        let called = &called;
+        drop(e.fn_mut(Box::new(move |()| {
+            let _ = &guard;
+            called.store(true, Ordering::Relaxed);
+        })));
+    });
+
+    assert!(!called.into_inner());
+    assert_eq!(dropped.into_inner(), 2);
+}
@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use scope_lock::SlotPool;
+
+#[test]
+fn extend_many_from_pool() {
+    let pool = SlotPool::<_, 4>::new();
+    let sum = AtomicUsize::new(0);
+
+    for round in 0..3 {
+        scope_lock::lock_scope(|e| {
+            for i in 0..4 {
+                let sum = &sum;
+                let f = e.fn_once(
+                    pool.try_insert(move |()| {
+                        sum.fetch_add(round * 4 + i, Ordering::Relaxed);
+                    })
+                    .unwrap_or_else(|_| panic!("pool is full")),
+                );
+                thread::spawn(move || f(()));
+            }
+        });
+    }
+
+    assert_eq!(sum.into_inner(), (0..12).sum());
+}
+
+#[test]
+fn slot_released_on_drop() {
+    let pool = SlotPool::<i32, 1>::new();
+    let a = pool.try_insert(1).unwrap();
+    assert_eq!(pool.try_insert(2).err(), Some(2));
+    drop(a);
+    let b = pool.try_insert(3).unwrap();
+    assert_eq!(scope_lock::PooledRefOnce::into_inner(b), 3);
+    assert!(pool.try_insert(4).is_ok());
+}
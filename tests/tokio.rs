@@ -0,0 +1,43 @@
+#![cfg(feature = "tokio")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::runtime::Builder;
+
+async fn run(counter: &AtomicUsize) {
+    let data = vec![1, 2, 3];
+
+    let handle = unsafe {
+        scope_lock::lock_scope_async(|e| {
+            for i in 0..data.len() {
+                let data = &data;
+                e.tokio_spawn(Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    counter.fetch_add(data[i], Ordering::Relaxed);
+                }));
+            }
+            e.tokio_spawn_blocking(Box::new(|()| data.len()))
+        })
+    }
+    .await;
+
+    assert_eq!(handle.await.unwrap(), 3);
+    assert_eq!(counter.load(Ordering::Relaxed), 6);
+}
+
+#[test]
+fn current_thread_runtime() {
+    let rt = Builder::new_current_thread().enable_time().build().unwrap();
+    rt.block_on(run(&AtomicUsize::new(0)));
+}
+
+#[test]
+fn multi_thread_runtime() {
+    let rt = Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_time()
+        .build()
+        .unwrap();
+    rt.block_on(run(&AtomicUsize::new(0)));
+}
@@ -0,0 +1,7 @@
+// Expected diagnostics are pinned to the toolchain of the UI test CI job
+#[test]
+#[cfg_attr(miri, ignore)]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
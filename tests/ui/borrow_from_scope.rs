@@ -0,0 +1,9 @@
+use std::thread;
+
+fn main() {
+    scope_lock::lock_scope(|e| {
+        let a = vec![1, 2, 3];
+        let f = e.fn_(Box::new(|()| a.len()));
+        thread::spawn(move || f(()));
+    });
+}
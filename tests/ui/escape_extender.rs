@@ -0,0 +1,4 @@
+fn main() {
+    let mut extender = None;
+    scope_lock::lock_scope(|e| extender = Some(e));
+}
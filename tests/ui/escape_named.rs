@@ -0,0 +1,5 @@
+fn main() {
+    let a = vec![1, 2, 3];
+    let f = |()| a.len();
+    let _f = scope_lock::lock_scope(|e| scope_lock::ExtendedFn::new(e, &f));
+}
@@ -0,0 +1,8 @@
+use std::cell::Cell;
+
+fn main() {
+    let counter = Cell::new(0);
+    scope_lock::lock_scope(|e| {
+        let _f = e.fn_(Box::new(|()| counter.set(counter.get() + 1)));
+    });
+}
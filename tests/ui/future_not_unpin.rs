@@ -0,0 +1,6 @@
+fn main() {
+    let mut fut = async {};
+    scope_lock::lock_scope(|e| {
+        let _fut = e.future(&mut fut);
+    });
+}
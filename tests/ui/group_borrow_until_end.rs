@@ -0,0 +1,13 @@
+use std::thread;
+
+fn main() {
+    let mut data = vec![1, 2, 3];
+    scope_lock::lock_scope(|e| {
+        e.group(|g| {
+            let f = g.fn_(Box::new(|()| data.len()));
+            thread::spawn(move || f(()));
+            g.wait();
+            data.push(4);
+        });
+    });
+}
@@ -0,0 +1,10 @@
+use std::rc::Rc;
+
+fn main() {
+    let a = Rc::new(1);
+    let f = |()| *a;
+    scope_lock::lock_scope(|e| {
+        let f = e.fn_local(&f);
+        std::thread::spawn(move || f(()));
+    });
+}
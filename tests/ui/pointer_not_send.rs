@@ -0,0 +1,7 @@
+use std::rc::Rc;
+
+fn main() {
+    scope_lock::lock_scope(|e| {
+        let _f = e.fn_(Rc::new(|()| ()));
+    });
+}
@@ -0,0 +1,15 @@
+use scope_lock::Extender;
+
+fn shorten_scope<'a, 'scope: 'a, 'env>(
+    e: &'a Extender<'scope, 'env>,
+) -> &'a Extender<'a, 'env> {
+    e
+}
+
+fn shorten_env<'scope, 'env: 'a, 'a>(
+    e: &'scope Extender<'scope, 'env>,
+) -> &'scope Extender<'scope, 'a> {
+    e
+}
+
+fn main() {}
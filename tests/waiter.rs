@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use scope_lock::{Job, Waiter};
+
+/// Minimal work queue, which the scope's thread helps to drain.
+#[derive(Clone, Default)]
+struct Queue(Arc<Mutex<VecDeque<Job>>>);
+
+impl Queue {
+    fn push(&self, job: Job) {
+        self.0.lock().unwrap().push_back(job);
+    }
+
+    fn pop(&self) -> Option<Job> {
+        self.0.lock().unwrap().pop_front()
+    }
+}
+
+impl Waiter for Queue {
+    fn try_help(&mut self) -> bool {
+        self.pop().map(|job| job()).is_some()
+    }
+}
+
+#[test]
+fn helps_with_queued_jobs() {
+    let queue = Queue::default();
+    let helped = AtomicUsize::new(0);
+    let scope_thread = thread::current().id();
+
+    scope_lock::lock_scope_with_waiter(queue.clone(), |e| {
+        for _ in 0..4 {
+            let helped = &helped;
+            let f = e.fn_once(Box::new(move |()| {
+                if thread::current().id() == scope_thread {
+                    helped.fetch_add(1, Ordering::Relaxed);
+                }
+            }));
+            queue.push(Box::new(move || f(())));
+        }
+    });
+
+    // No worker threads, so the scope had to run every job itself
+    assert_eq!(helped.into_inner(), 4);
+}
+
+#[test]
+fn falls_back_to_blocking() {
+    let mut x = 0;
+
+    scope_lock::lock_scope_with_waiter(
+        || false,
+        |e| {
+            let mut f = e.fn_mut(Box::new(|()| x += 1));
+            thread::spawn(move || f(()));
+        },
+    );
+
+    assert_eq!(x, 1);
+}
+
+#[test]
+fn helps_with_jobs_queued_later() {
+    let queue = Queue::default();
+    let helped = AtomicUsize::new(0);
+    let scope_thread = thread::current().id();
+
+    scope_lock::lock_scope_with_waiter(queue.clone(), |e| {
+        let helped = &helped;
+        let job = e.fn_once(Box::new(move |()| {
+            if thread::current().id() == scope_thread {
+                helped.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+        // queue runs dry before the job is pushed
+        let push = e.fn_once(Box::new(move |()| {
+            thread::sleep(Duration::from_millis(20));
+            queue.push(Box::new(move || job(())));
+        }));
+        thread::spawn(move || push(()));
+    });
+
+    // Nobody else drains the queue, so the scope had to keep helping
+    assert_eq!(helped.into_inner(), 1);
+}
@@ -0,0 +1,117 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use scope_lock::WakeRef;
+
+struct Scheduler {
+    ready: Mutex<Vec<usize>>,
+}
+
+struct TaskWaker<'a> {
+    id: usize,
+    scheduler: &'a Scheduler,
+}
+
+impl WakeRef for TaskWaker<'_> {
+    fn wake_by_ref(&self) {
+        self.scheduler.ready.lock().unwrap().push(self.id);
+    }
+}
+
+#[test]
+fn wake_borrowed_scheduler() {
+    let scheduler = Scheduler {
+        ready: Mutex::new(Vec::new()),
+    };
+
+    scope_lock::lock_scope(|e| {
+        for id in 0..4 {
+            let waker = e.waker(Box::new(TaskWaker {
+                id,
+                scheduler: &scheduler,
+            }));
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                waker.wake();
+            });
+        }
+    });
+
+    let mut ready = scheduler.ready.into_inner().unwrap();
+    ready.sort();
+    assert_eq!(ready, [0, 1, 2, 3]);
+}
+
+#[test]
+fn waits_for_clones() {
+    struct Counter<'a>(&'a AtomicUsize);
+
+    impl WakeRef for Counter<'_> {
+        fn wake_by_ref(&self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let wakes = AtomicUsize::new(0);
+    let counter = Counter(&wakes);
+
+    scope_lock::lock_scope(|e| {
+        let waker = e.waker(&counter);
+        let clone = waker.clone();
+        assert!(waker.will_wake(&clone));
+        waker.wake_by_ref();
+        drop(waker);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            let clones = [clone.clone(), clone];
+            clones.iter().for_each(|w| w.wake_by_ref());
+        });
+    });
+
+    assert_eq!(wakes.into_inner(), 3);
+}
+
+#[test]
+fn drops_pointer() {
+    struct Flag<'a>(&'a Mutex<bool>);
+
+    impl WakeRef for Flag<'_> {
+        fn wake_by_ref(&self) {}
+    }
+
+    impl Drop for Flag<'_> {
+        fn drop(&mut self) {
+            *self.0.lock().unwrap() = true;
+        }
+    }
+
+    let dropped = Mutex::new(false);
+
+    scope_lock::lock_scope(|e| {
+        let waker = e.waker(Box::new(Flag(&dropped)));
+        thread::spawn(move || waker.wake());
+    });
+
+    assert!(dropped.into_inner().unwrap());
+}
+
+#[test]
+fn released_on_wake_panic() {
+    struct Panicking;
+
+    impl WakeRef for Panicking {
+        fn wake_by_ref(&self) {
+            panic!("expected panic");
+        }
+    }
+
+    let handle = scope_lock::lock_scope(|e| {
+        let waker = e.waker(Box::new(Panicking));
+        thread::spawn(move || waker.wake())
+    });
+
+    // scope has ended, so the waker was released despite the panic
+    assert!(handle.join().is_err());
+}
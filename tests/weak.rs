@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use scope_lock::{WeakFn, WeakFnMut};
+
+#[test]
+fn revoked_after_scope() {
+    let registry: Mutex<Vec<WeakFn<usize, usize>>> = Mutex::new(Vec::new());
+    let mut calls = 0;
+    let mut_registry: Mutex<Vec<WeakFnMut<(), ()>>> = Mutex::new(Vec::new());
+    let data = [1, 2, 3];
+
+    scope_lock::lock_scope(|e| {
+        let f = e.fn_weak(Box::new(|i| data[i]));
+        assert_eq!(f.call(1), Some(2));
+        registry.lock().unwrap().push(f.clone());
+
+        let f = e.fn_mut_weak(Box::new(|()| calls += 1));
+        assert_eq!(f.call(()), Some(()));
+        mut_registry.lock().unwrap().push(f);
+    });
+
+    let registry = registry.into_inner().unwrap();
+    assert!(registry[0].is_revoked());
+    assert_eq!(registry[0].call(0), None);
+    let mut_registry = mut_registry.into_inner().unwrap();
+    assert_eq!(mut_registry[0].call(()), None);
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn dropped_by_scope() {
+    struct Guard<'a>(&'a AtomicUsize);
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = AtomicUsize::new(0);
+    let guard = Guard(&dropped);
+    let f = move |()| {
+        let _ = &guard;
+    };
+
+    let handle = scope_lock::lock_scope(|e| e.fn_weak(Box::new(f)));
+
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    drop(handle);
+    assert_eq!(dropped.into_inner(), 1);
+}
+
+#[test]
+fn waits_for_calls_in_flight() {
+    let done = AtomicBool::new(false);
+    let barrier = Barrier::new(2);
+
+    let worker = scope_lock::lock_scope(|e| {
+        let f = e.fn_weak(Box::new(|()| {
+            barrier.wait();
+            thread::sleep(Duration::from_millis(20));
+            done.store(true, Ordering::Relaxed);
+        }));
+        let worker = thread::spawn(move || f.call(()));
+        barrier.wait();
+        worker
+    });
+
+    assert!(done.into_inner());
+    assert_eq!(worker.join().unwrap(), Some(()));
+}
+
+#[test]
+fn registered_while_ending() {
+    let data = [1, 2, 3];
+    let late = Mutex::new(None);
+
+    scope_lock::lock_scope(|e| {
+        let (data, late) = (&data, &late);
+        let f = e.fn_once(Box::new(move |()| {
+            thread::sleep(Duration::from_millis(20));
+            *late.lock().unwrap() = Some(e.fn_weak(Box::new(move |()| data.len())));
+        }));
+        thread::spawn(move || f(()));
+    });
+
+    let late = late.into_inner().unwrap().unwrap();
+    assert!(late.is_revoked());
+}